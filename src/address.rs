@@ -227,6 +227,25 @@ impl Reference {
 			Err(Error::UnresolvedReferenceComponent)
 		}
 	} 
+
+	/// Returns whether the given `Address` lies within the referenced cells.
+	/// Named and indirect components are not resolved, and never match.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::{Address, Reference};
+	/// 
+	/// let page = Reference::page_of(&Address::new(2, 0, 0));
+	///
+	/// assert!(page.contains(&Address::new(2, 5, 7)));
+	/// assert!(!page.contains(&Address::new(3, 0, 0)));
+	/// ```
+	pub fn contains(&self, address: &Address) -> bool {
+		self.page.matches(address.page) &&
+		self.line.matches(address.line) &&
+		self.column.matches(address.column)
+	}
 }
 
 
@@ -278,6 +297,19 @@ enum ReferenceComponent<T, O> {
 	All,
 }
 
+impl<T, O> ReferenceComponent<T, O> where T: PartialEq {
+	/// Returns whether the given index lies within the component.
+	fn matches(&self, index: T) -> bool {
+		use self::ReferenceComponent::*;
+
+		match *self {
+			Any | All		=> true,
+			Index(ref i)	=> *i == index,
+			_				=> false,
+		}
+	}
+}
+
 impl<T, O> ReferenceComponent<T, O>
 	where 
 		O: Offset<Base=T>,
@@ -448,6 +480,45 @@ impl Address {
 			(m % c) as Column
 		)
 	}
+
+	/// Returns the `Address` n steps behind, assuming the given wrapping 
+	/// parameters.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::Address;
+	/// 
+	/// let a = Address::new(1, 0, 0);
+	/// let b = a.wrapping_step_back(1, 10, 10, 10);
+	/// 
+	/// assert_eq!(b, Address::new(0, 9, 9));
+	///
+	/// let c = Address::new(0, 0, 0).wrapping_step_back(1, 10, 10, 10);
+	/// assert_eq!(c, Address::new(9, 9, 9));
+	/// ```
+	pub fn wrapping_step_back(
+		&self, 
+		n: usize,
+		pages: Page,
+		lines: Line, 
+		columns: Column) 
+		-> Address
+	{
+		let (l, c) = (lines as usize, columns as usize);
+		let total = pages as usize * l * c;
+		let n1 = self.page as usize * l * c
+			+ self.line as usize * c
+			+ self.column as usize;
+		let n2 = (n1 % total + total - n % total) % total;
+		let d = n2 / (l * c);
+		let m = n2 % (l * c);
+		Address::new(
+			d as Page,
+			(m / c) as Line,
+			(m % c) as Column
+		)
+	}
 }
 
 
@@ -479,13 +550,70 @@ impl fmt::LowerHex for Address {
 }
 
 
+/// Returns an iterator over the addresses in the given interval, in address 
+/// order, assuming the given wrapping parameters. Open endpoints of the 
+/// interval are excluded.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate interval;
+/// # extern crate palette;
+/// use palette::Address;
+/// use palette::address::interval_addresses;
+/// use interval::Interval;
+///
+/// # fn main() {
+/// let (a, b) = (Address::new(0, 0, 14), Address::new(0, 1, 1));
+///
+/// let closed: Vec<_> = interval_addresses(&Interval::closed(a, b), 1, 2, 16)
+/// 	.collect();
+/// assert_eq!(closed, vec![
+/// 	Address::new(0, 0, 14),
+/// 	Address::new(0, 0, 15),
+/// 	Address::new(0, 1, 0),
+/// 	Address::new(0, 1, 1),
+/// ]);
+///
+/// let right_open: Vec<_> = interval_addresses(
+/// 		&Interval::right_open(a, b), 1, 2, 16)
+/// 	.collect();
+/// assert_eq!(right_open.first(), Some(&a));
+/// assert_eq!(right_open.last(), Some(&Address::new(0, 1, 0)));
+/// # }
+/// ```
+pub fn interval_addresses(
+	interval: &Interval<Address>,
+	pages: Page,
+	lines: Line,
+	columns: Column)
+	-> impl Iterator<Item=Address>
+{
+	let interval = interval.clone();
+	let (start, end) = (interval.left_point(), interval.right_point());
+
+	// Step no further than the last address or the end of the interval, so 
+	// that the addresses never wrap back around to the first page.
+	let (l, c) = (lines as usize, columns as usize);
+	let index = |a: Address| 
+		a.page as usize * l * c + a.line as usize * c + a.column as usize;
+	let (first, last) = (index(start), index(end));
+	let last = usize::min(last, (pages as usize * l * c).saturating_sub(1));
+	let steps = if end < start || last < first { 0 } else { last - first + 1 };
+
+	(0..steps)
+		.map(move |n| start.wrapping_step(n, pages, lines, columns))
+		.filter(move |address| interval.contains(address))
+}
+
+
 
 
 ////////////////////////////////////////////////////////////////////////////////
 // Selection
 ////////////////////////////////////////////////////////////////////////////////
 /// A possibly non-contiguous selection of addresses.
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Selection {
 	inner: Vec<Interval<Address>>
 }
@@ -501,6 +629,56 @@ impl Selection {
 		}
 	}
 
+	/// Creates a new selection containing every address.
+	pub fn everything() -> Self {
+		Selection::new(vec![Interval::closed(
+			Address::new(0, 0, 0),
+			Address::new(PAGE_MAX, LINE_MAX, COLUMN_MAX)
+		)])
+	}
+
+	/// Creates a new selection containing every address on the given page.
+	pub fn page(page: Page) -> Self {
+		Selection::new(vec![Interval::closed(
+			Address::new(page, 0, 0),
+			Address::new(page, LINE_MAX, COLUMN_MAX)
+		)])
+	}
+
+	/// Creates a new selection containing every address on the given line.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::Address;
+	/// use palette::address::Selection;
+	///
+	/// let line = Selection::line(2, 3);
+	/// 
+	/// assert!(line.contains(&Address::new(2, 3, 0)));
+	/// assert!(line.contains(&Address::new(2, 3, 255)));
+	/// assert!(!line.contains(&Address::new(2, 2, 255)));
+	/// assert!(!line.contains(&Address::new(2, 4, 0)));
+	/// assert!(!line.contains(&Address::new(3, 3, 0)));
+	/// ```
+	pub fn line(page: Page, line: Line) -> Self {
+		Selection::new(vec![Interval::closed(
+			Address::new(page, line, 0),
+			Address::new(page, line, COLUMN_MAX)
+		)])
+	}
+
+	/// Creates a new selection containing the columns from `start` to `end`, 
+	/// inclusive, on the given line.
+	pub fn column_range(page: Page, line: Line, start: Column, end: Column) 
+		-> Self
+	{
+		Selection::new(vec![Interval::closed(
+			Address::new(page, line, start),
+			Address::new(page, line, end)
+		)])
+	}
+
 	/// Unions an interval into the selection.
 	pub fn union(&mut self, interval: Interval<Address>) {
 		self.inner.push(interval);
@@ -510,4 +688,129 @@ impl Selection {
 	pub fn contains(&self, address: &Address) -> bool {
 		self.inner.iter().any(|int| int.contains(address))
 	}
-}
\ No newline at end of file
+
+	/// Returns the first and last addresses contained in the selection, or None
+	/// if the selection is empty. Open interval endpoints are excluded.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # extern crate interval;
+	/// # extern crate palette;
+	/// use palette::Address;
+	/// use palette::address::Selection;
+	/// use interval::Interval;
+	///
+	/// # fn main() {
+	/// let selection = Selection::new(vec![
+	/// 	Interval::closed(Address::new(0, 1, 2), Address::new(0, 1, 5)),
+	/// 	Interval::right_open(Address::new(2, 0, 0), Address::new(2, 0, 4)),
+	/// ]);
+	/// assert_eq!(selection.bounds(), 
+	/// 	Some((Address::new(0, 1, 2), Address::new(2, 0, 3))));
+	///
+	/// assert_eq!(Selection::default().bounds(), None);
+	/// # }
+	/// ```
+	pub fn bounds(&self) -> Option<(Address, Address)> {
+		let mut bounds: Option<(Address, Address)> = None;
+		for interval in &self.inner {
+			let mut start = interval.left_point();
+			let mut end = interval.right_point();
+			if !interval.contains(&start) {
+				match successor(start) {
+					Some(address) => start = address,
+					None => continue,
+				}
+			}
+			if !interval.contains(&end) {
+				match predecessor(end) {
+					Some(address) => end = address,
+					None => continue,
+				}
+			}
+			if start > end { continue; }
+
+			bounds = Some(match bounds {
+				Some((first, last)) => (first.min(start), last.max(end)),
+				None => (start, end),
+			});
+		}
+		bounds
+	}
+
+	/// Returns the group `Reference` covering exactly the same addresses as the 
+	/// selection, or None if the selection is not exactly a line, a page, or 
+	/// every address.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # extern crate interval;
+	/// # extern crate palette;
+	/// use palette::{Address, Reference};
+	/// use palette::address::Selection;
+	/// use interval::Interval;
+	///
+	/// # fn main() {
+	/// let line = Selection::line(2, 3);
+	/// assert_eq!(line.as_group(), Some(Reference::line_of(&Address::new(2, 3, 0))));
+	///
+	/// let partial = Selection::new(vec![
+	/// 	Interval::closed(Address::new(2, 3, 0), Address::new(2, 3, 5))
+	/// ]);
+	/// assert_eq!(partial.as_group(), None);
+	/// # }
+	/// ```
+	pub fn as_group(&self) -> Option<Reference> {
+		if self.inner.len() != 1 { return None; }
+		let interval = &self.inner[0];
+		let (start, end) = (interval.left_point(), interval.right_point());
+		if *interval != Interval::closed(start, end) { return None; }
+
+		if start == Address::new(0, 0, 0) && 
+			end == Address::new(PAGE_MAX, LINE_MAX, COLUMN_MAX)
+		{
+			Some(Reference::all())
+		} else if start == Address::new(start.page, 0, 0) && 
+			end == Address::new(start.page, LINE_MAX, COLUMN_MAX)
+		{
+			Some(Reference::page_of(&start))
+		} else if start.column == 0 && 
+			end == Address::new(start.page, start.line, COLUMN_MAX)
+		{
+			Some(Reference::line_of(&start))
+		} else {
+			None
+		}
+	}
+}
+
+
+/// Returns the address following the given address in address order, or None
+/// if it is the last address.
+fn successor(address: Address) -> Option<Address> {
+	if address.column < COLUMN_MAX {
+		Some(Address::new(address.page, address.line, address.column + 1))
+	} else if address.line < LINE_MAX {
+		Some(Address::new(address.page, address.line + 1, 0))
+	} else if address.page < PAGE_MAX {
+		Some(Address::new(address.page + 1, 0, 0))
+	} else {
+		None
+	}
+}
+
+/// Returns the address preceding the given address in address order, or None
+/// if it is the first address.
+fn predecessor(address: Address) -> Option<Address> {
+	if address.column > 0 {
+		Some(Address::new(address.page, address.line, address.column - 1))
+	} else if address.line > 0 {
+		Some(Address::new(address.page, address.line - 1, COLUMN_MAX))
+	} else if address.page > 0 {
+		Some(Address::new(address.page - 1, LINE_MAX, COLUMN_MAX))
+	} else {
+		None
+	}
+}
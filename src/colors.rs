@@ -0,0 +1,1280 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides general purpose functions for working with `Color`s outside of the
+//! palette structure.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use utilities::{clamped, lerp_f32, RoundingMode};
+
+// Non-local imports.
+use color::Color;
+
+
+
+/// Returns the red, green, and blue octets of the given color.
+#[inline]
+pub fn octets(color: Color) -> [u8; 3] {
+	color.rgb().octets()
+}
+
+/// Returns the red octet of the given color.
+#[inline]
+pub fn red(color: Color) -> u8 {
+	octets(color)[0]
+}
+
+/// Returns the green octet of the given color.
+#[inline]
+pub fn green(color: Color) -> u8 {
+	octets(color)[1]
+}
+
+/// Returns the blue octet of the given color.
+#[inline]
+pub fn blue(color: Color) -> u8 {
+	octets(color)[2]
+}
+
+/// Returns a copy of the given color with its red octet replaced.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::{blue, green, red, with_red};
+///
+/// let color = with_red(Color::new(10, 20, 30), 200);
+///
+/// assert_eq!(color, Color::new(200, 20, 30));
+/// assert_eq!((red(color), green(color), blue(color)), (200, 20, 30));
+/// ```
+#[inline]
+pub fn with_red(color: Color, red: u8) -> Color {
+	let [_, g, b] = octets(color);
+	Color::new(red, g, b)
+}
+
+/// Returns a copy of the given color with its green octet replaced.
+#[inline]
+pub fn with_green(color: Color, green: u8) -> Color {
+	let [r, _, b] = octets(color);
+	Color::new(r, green, b)
+}
+
+/// Returns a copy of the given color with its blue octet replaced.
+#[inline]
+pub fn with_blue(color: Color, blue: u8) -> Color {
+	let [r, g, _] = octets(color);
+	Color::new(r, g, blue)
+}
+
+/// Returns the red, green, and blue components of the given color as ratios
+/// between 0 and 1.
+#[inline]
+pub fn ratios(color: Color) -> [f32; 3] {
+	let [r, g, b] = octets(color);
+	[r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0]
+}
+
+/// Returns the color with the given red, green, and blue ratios. The ratios are
+/// clamped between 0 and 1.
+#[inline]
+pub fn from_ratios(ratios: [f32; 3]) -> Color {
+	from_ratios_mapped(ratios, GamutMap::ClampChannel)
+}
+
+/// Returns the color with the given red, green, and blue ratios, using the 
+/// given `GamutMap` to bring them between 0 and 1.
+#[inline]
+pub fn from_ratios_mapped(ratios: [f32; 3], gamut_map: GamutMap) -> Color {
+	from_ratios_with(ratios, gamut_map, RoundingMode::default())
+}
+
+/// Returns the color with the given red, green, and blue ratios, using the 
+/// given `GamutMap` to bring them between 0 and 1 and the given 
+/// `RoundingMode` to convert them to octets.
+pub fn from_ratios_with(
+	ratios: [f32; 3], 
+	gamut_map: GamutMap, 
+	rounding: RoundingMode) 
+	-> Color
+{
+	let ratios = gamut_map.map(ratios);
+	let quantize = |v: f32| rounding.quantize(clamped(v, 0.0, 1.0) * 255.0);
+	Color::new(quantize(ratios[0]), quantize(ratios[1]), quantize(ratios[2]))
+}
+
+
+/// Returns the relative luminance of the given color, as defined by WCAG 2.0.
+/// The result lies between 0 (black) and 1 (white).
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::relative_luminance;
+///
+/// assert!((relative_luminance(Color::new(255, 255, 255)) - 1.0).abs() 
+/// 	< 0.0001);
+/// assert_eq!(relative_luminance(Color::new(0, 0, 0)), 0.0);
+/// assert!((relative_luminance(Color::new(128, 128, 128)) - 0.2159).abs() 
+/// 	< 0.0001);
+/// ```
+pub fn relative_luminance(color: Color) -> f32 {
+	let [r, g, b] = linear_ratios(color);
+	0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Returns the WCAG 2.0 contrast ratio between the given colors. The result 
+/// lies between 1 (no contrast) and 21 (black against white).
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::contrast_ratio;
+///
+/// let black = Color::new(0, 0, 0);
+/// let white = Color::new(255, 255, 255);
+///
+/// assert!((contrast_ratio(black, white) - 21.0).abs() < 0.01);
+/// assert!((contrast_ratio(white, black) - 21.0).abs() < 0.01);
+/// assert_eq!(contrast_ratio(white, white), 1.0);
+/// ```
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+	let (la, lb) = (relative_luminance(a), relative_luminance(b));
+	(f32::max(la, lb) + 0.05) / (f32::min(la, lb) + 0.05)
+}
+
+/// Returns black or white, whichever has the higher contrast ratio against 
+/// the given color. This is useful for choosing a legible text color to draw 
+/// over a swatch.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::contrast_color;
+///
+/// let black = Color::new(0, 0, 0);
+/// let white = Color::new(255, 255, 255);
+///
+/// assert_eq!(contrast_color(Color::new(20, 30, 60)), white);
+/// assert_eq!(contrast_color(Color::new(240, 230, 180)), black);
+/// assert_eq!(contrast_color(black), white);
+/// assert_eq!(contrast_color(white), black);
+/// ```
+pub fn contrast_color(color: Color) -> Color {
+	let black = Color::new(0, 0, 0);
+	let white = Color::new(255, 255, 255);
+	if contrast_ratio(color, white) > contrast_ratio(color, black) {
+		white
+	} else {
+		black
+	}
+}
+
+/// Returns the hue, saturation, and lightness of the given color. The hue is 
+/// given in degrees between 0 and 360, and the saturation and lightness are 
+/// given as ratios between 0 and 1.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::hsl;
+///
+/// let [h, s, l] = hsl(Color::new(0, 0, 255));
+///
+/// assert_eq!((h, s, l), (240.0, 1.0, 0.5));
+/// ```
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+pub fn hsl(color: Color) -> [f32; 3] {
+	let [r, g, b] = ratios(color);
+	let max = f32::max(r, f32::max(g, b));
+	let min = f32::min(r, f32::min(g, b));
+	let l = (max + min) / 2.0;
+	if max == min {
+		return [0.0, 0.0, l];
+	}
+
+	let d = max - min;
+	let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+	let h = if max == r {
+		(g - b) / d + if g < b { 6.0 } else { 0.0 }
+	} else if max == g {
+		(b - r) / d + 2.0
+	} else {
+		(r - g) / d + 4.0
+	};
+	[h * 60.0, s, l]
+}
+
+/// Returns the color with the given hue, saturation, and lightness. The hue is
+/// given in degrees and wraps at 360, and the saturation and lightness are 
+/// clamped between 0 and 1. Use `from_hsl_mapped` to bring over-saturated 
+/// colors into gamut with a `GamutMap` instead.
+pub fn from_hsl(hsl: [f32; 3]) -> Color {
+	from_hsl_mapped(
+		[hsl[0], clamped(hsl[1], 0.0, 1.0), hsl[2]], 
+		GamutMap::ClampChannel)
+}
+
+/// Returns the color with the given hue, saturation, and lightness, using the
+/// given `GamutMap` to bring the resulting RGB ratios between 0 and 1. The hue 
+/// is given in degrees and wraps at 360, and the lightness is clamped between 
+/// 0 and 1. Saturation above 1 extends the chroma of the fully saturated 
+/// color beyond the sRGB gamut.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::colors::{from_hsl, from_hsl_mapped, hsl, GamutMap};
+///
+/// assert_eq!(
+/// 	from_hsl_mapped([0.0, 1.0, 0.5], GamutMap::PreserveHue), 
+/// 	from_hsl([0.0, 1.0, 0.5]));
+///
+/// // An over-saturated orange keeps its hue only when the hue is preserved.
+/// let orange = [45.0, 1.5, 0.5];
+/// let clamped = from_hsl_mapped(orange, GamutMap::ClampChannel);
+/// let preserved = from_hsl_mapped(orange, GamutMap::PreserveHue);
+///
+/// assert!((hsl(preserved)[0] - 45.0).abs() < 1.0);
+/// assert!((hsl(clamped)[0] - 45.0).abs() > 5.0);
+/// ```
+#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+pub fn from_hsl_mapped(hsl: [f32; 3], gamut_map: GamutMap) -> Color {
+	let h = (hsl[0] % 360.0 + 360.0) % 360.0 / 360.0;
+	let s = clamped(hsl[1], 0.0, 1.0);
+	let l = clamped(hsl[2], 0.0, 1.0);
+	if s == 0.0 {
+		return from_ratios([l, l, l]);
+	}
+	// Extends the chroma of a fully saturated channel by the saturation.
+	let extend = |c: f32| if hsl[1] <= 1.0 { c } else { l + (c - l) * hsl[1] };
+
+	let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+	let p = 2.0 * l - q;
+	let channel = |t: f32| {
+		let t = if t < 0.0 { t + 1.0 } else if t > 1.0 { t - 1.0 } else { t };
+		if t < 1.0 / 6.0 {
+			p + (q - p) * 6.0 * t
+		} else if t < 0.5 {
+			q
+		} else if t < 2.0 / 3.0 {
+			p + (q - p) * (2.0 / 3.0 - t) * 6.0
+		} else {
+			p
+		}
+	};
+	from_ratios_mapped([
+			extend(channel(h + 1.0 / 3.0)), 
+			extend(channel(h)), 
+			extend(channel(h - 1.0 / 3.0)),
+		], 
+		gamut_map)
+}
+
+/// Returns the channel-wise sum of the given colors, saturating at 255.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::saturating_add;
+///
+/// assert_eq!(
+/// 	saturating_add(Color::new(200, 100, 0), Color::new(100, 100, 10)),
+/// 	Color::new(255, 200, 10));
+/// ```
+pub fn saturating_add(a: Color, b: Color) -> Color {
+	let [ar, ag, ab] = octets(a);
+	let [br, bg, bb] = octets(b);
+	Color::new(
+		ar.saturating_add(br), 
+		ag.saturating_add(bg), 
+		ab.saturating_add(bb))
+}
+
+/// Returns the channel-wise difference of the given colors, saturating at 0.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::saturating_sub;
+///
+/// assert_eq!(
+/// 	saturating_sub(Color::new(200, 100, 0), Color::new(100, 150, 10)),
+/// 	Color::new(100, 0, 0));
+/// ```
+pub fn saturating_sub(a: Color, b: Color) -> Color {
+	let [ar, ag, ab] = octets(a);
+	let [br, bg, bb] = octets(b);
+	Color::new(
+		ar.saturating_sub(br), 
+		ag.saturating_sub(bg), 
+		ab.saturating_sub(bb))
+}
+
+/// Returns the given color with each channel multiplied by the given factor,
+/// clamped between 0 and 255.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::scale;
+///
+/// assert_eq!(scale(Color::new(200, 100, 50), 0.5), Color::new(100, 50, 25));
+/// assert_eq!(scale(Color::new(200, 100, 50), 2.0), Color::new(255, 200, 100));
+/// ```
+pub fn scale(color: Color, factor: f32) -> Color {
+	let [r, g, b] = ratios(color);
+	from_ratios([r * factor, g * factor, b * factor])
+}
+
+/// Returns the cyan, magenta, yellow, and key (black) components of the given
+/// color as ratios between 0 and 1, using the naive conversion without a color
+/// profile.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::{cmyk, from_cmyk};
+///
+/// assert_eq!(cmyk(Color::new(255, 0, 0)), [0.0, 1.0, 1.0, 0.0]);
+/// assert_eq!(cmyk(Color::new(0, 0, 0)), [0.0, 0.0, 0.0, 1.0]);
+///
+/// let color = Color::new(12, 150, 78);
+/// assert_eq!(from_cmyk(cmyk(color)), color);
+/// ```
+pub fn cmyk(color: Color) -> [f32; 4] {
+	let [r, g, b] = ratios(color);
+	let k = 1.0 - f32::max(r, f32::max(g, b));
+	if k >= 1.0 {
+		return [0.0, 0.0, 0.0, 1.0];
+	}
+	let c = |v: f32| (1.0 - v - k) / (1.0 - k);
+	[c(r), c(g), c(b), k]
+}
+
+/// Returns the color with the given cyan, magenta, yellow, and key (black)
+/// ratios, using the naive conversion without a color profile. The ratios are
+/// clamped between 0 and 1.
+pub fn from_cmyk(cmyk: [f32; 4]) -> Color {
+	let k = clamped(cmyk[3], 0.0, 1.0);
+	let c = |v: f32| (1.0 - clamped(v, 0.0, 1.0)) * (1.0 - k);
+	from_ratios([c(cmyk[0]), c(cmyk[1]), c(cmyk[2])])
+}
+
+/// Returns whether each channel of the given colors differs by no more than
+/// `tolerance`. Useful for comparing colors which have passed through floating
+/// point conversions.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::approx_eq;
+///
+/// let color = Color::new(100, 150, 200);
+///
+/// assert!(approx_eq(color, Color::new(101, 149, 200), 1));
+/// assert!(!approx_eq(color, Color::new(102, 150, 200), 1));
+/// ```
+pub fn approx_eq(a: Color, b: Color, tolerance: u8) -> bool {
+	octets(a)
+		.iter()
+		.zip(octets(b).iter())
+		.all(|(&a, &b)| u8::max(a, b) - u8::min(a, b) <= tolerance)
+}
+
+/// Panics if any channel of the given colors differs by more than `tolerance`.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::{assert_approx_eq, from_lab, lab};
+///
+/// let color = Color::new(100, 150, 200);
+/// assert_approx_eq(from_lab(lab(color)), color, 1);
+/// ```
+///
+/// ```rust,should_panic
+/// use palette::Color;
+/// use palette::colors::assert_approx_eq;
+///
+/// assert_approx_eq(Color::new(100, 150, 200), Color::new(103, 150, 200), 1);
+/// ```
+pub fn assert_approx_eq(a: Color, b: Color, tolerance: u8) {
+	if !approx_eq(a, b, tolerance) {
+		panic!("colors {} and {} differ by more than {}", a, b, tolerance);
+	}
+}
+
+/// Returns whether the channels of the given color all lie within `tolerance`
+/// of each other.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::is_grayscale;
+///
+/// assert!(is_grayscale(Color::new(128, 128, 128), 0));
+/// assert!(is_grayscale(Color::new(128, 130, 127), 3));
+/// assert!(!is_grayscale(Color::new(128, 130, 127), 2));
+/// assert!(!is_grayscale(Color::new(255, 0, 0), 16));
+/// ```
+pub fn is_grayscale(color: Color, tolerance: u8) -> bool {
+	let [r, g, b] = octets(color);
+	let max = u8::max(r, u8::max(g, b));
+	let min = u8::min(r, u8::min(g, b));
+	max - min <= tolerance
+}
+
+/// Returns the gray with the same luma as the given color, using the Rec. 601
+/// luma coefficients.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::to_grayscale;
+///
+/// assert_eq!(to_grayscale(Color::new(90, 90, 90)), Color::new(90, 90, 90));
+/// assert_eq!(to_grayscale(Color::new(255, 0, 0)), Color::new(76, 76, 76));
+/// ```
+pub fn to_grayscale(color: Color) -> Color {
+	let [r, g, b] = ratios(color);
+	let y = 0.299 * r + 0.587 * g + 0.114 * b;
+	from_ratios([y, y, y])
+}
+
+/// Returns the per-channel mean of the given colors, or None if there are no 
+/// colors.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::checked_mix_many;
+///
+/// let primaries = [
+/// 	Color::new(255, 0, 0), 
+/// 	Color::new(0, 255, 0), 
+/// 	Color::new(0, 0, 255),
+/// ];
+///
+/// assert_eq!(checked_mix_many(&primaries), Some(Color::new(85, 85, 85)));
+/// assert_eq!(checked_mix_many(&[]), None);
+/// ```
+pub fn checked_mix_many(colors: &[Color]) -> Option<Color> {
+	if colors.is_empty() { return None; }
+
+	let mut sums = [0u32; 3];
+	for &color in colors {
+		for (sum, &c) in sums.iter_mut().zip(octets(color).iter()) {
+			*sum += u32::from(c);
+		}
+	}
+	let n = colors.len() as f32;
+	let mean = |sum: u32| RoundingMode::default().quantize(sum as f32 / n);
+	Some(Color::new(mean(sums[0]), mean(sums[1]), mean(sums[2])))
+}
+
+/// Returns the per-channel mean of the given colors, or black if there are no
+/// colors.
+#[inline]
+pub fn mix_many(colors: &[Color]) -> Color {
+	checked_mix_many(colors).unwrap_or_else(|| Color::new(0, 0, 0))
+}
+
+/// Returns an iterator over `count` evenly spaced colors between the given 
+/// colors, including both endpoints. If `count` is 1, only the first color is
+/// produced.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::ramp_iter;
+///
+/// let samples: Vec<_> = ramp_iter(
+/// 		Color::new(0, 0, 0), 
+/// 		Color::new(255, 255, 255), 
+/// 		5)
+/// 	.collect();
+///
+/// assert_eq!(samples.len(), 5);
+/// assert_eq!(samples[0], Color::new(0, 0, 0));
+/// assert_eq!(samples[2], Color::new(128, 128, 128));
+/// assert_eq!(samples[4], Color::new(255, 255, 255));
+/// ```
+pub fn ramp_iter(from: Color, to: Color, count: usize) 
+	-> impl Iterator<Item=Color>
+{
+	let steps = count.saturating_sub(1).max(1) as f32;
+	(0..count).map(move |i| 
+		blend(from, to, BlendMode::Normal, i as f32 / steps))
+}
+
+/// Returns the hue, saturation, and value of the given color. The hue is given
+/// in degrees between 0 and 360, and the saturation and value are given as 
+/// ratios between 0 and 1.
+pub fn hsv(color: Color) -> [f32; 3] {
+	let [r, g, b] = ratios(color);
+	let max = f32::max(r, f32::max(g, b));
+	let min = f32::min(r, f32::min(g, b));
+	let s = if max > 0.0 { (max - min) / max } else { 0.0 };
+	[hsl(color)[0], s, max]
+}
+
+/// Returns the color with the given hue, saturation, and value. The hue is 
+/// given in degrees and wraps at 360, and the saturation and value are clamped
+/// between 0 and 1.
+pub fn from_hsv(hsv: [f32; 3]) -> Color {
+	let h = (hsv[0] % 360.0 + 360.0) % 360.0 / 60.0;
+	let s = clamped(hsv[1], 0.0, 1.0);
+	let v = clamped(hsv[2], 0.0, 1.0);
+	let c = v * s;
+	let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+	let (r, g, b) = match h as u8 {
+		0 => (c, x, 0.0),
+		1 => (x, c, 0.0),
+		2 => (0.0, c, x),
+		3 => (0.0, x, c),
+		4 => (x, 0.0, c),
+		_ => (c, 0.0, x),
+	};
+	let m = v - c;
+	from_ratios([r + m, g + m, b + m])
+}
+
+/// Returns the given sRGB channel ratio converted to linear light.
+fn to_linear(c: f32) -> f32 {
+	if c <= 0.04045 {
+		c / 12.92
+	} else {
+		((c + 0.055) / 1.055).powf(2.4)
+	}
+}
+
+/// Returns the given linear light channel ratio converted to sRGB.
+fn from_linear(c: f32) -> f32 {
+	if c <= 0.003_130_8 {
+		c * 12.92
+	} else {
+		1.055 * c.powf(1.0 / 2.4) - 0.055
+	}
+}
+
+/// Returns the linear-light red, green, and blue ratios of the given color.
+pub fn linear_ratios(color: Color) -> [f32; 3] {
+	let [r, g, b] = ratios(color);
+	[to_linear(r), to_linear(g), to_linear(b)]
+}
+
+/// Returns the color with the given linear-light red, green, and blue ratios.
+/// The ratios are clamped between 0 and 1.
+pub fn from_linear_ratios(ratios: [f32; 3]) -> Color {
+	let [r, g, b] = ratios;
+	let c = |v: f32| from_linear(clamped(v, 0.0, 1.0));
+	from_ratios([c(r), c(g), c(b)])
+}
+
+/// The CIE reference white point (D65).
+const WHITE_POINT: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Returns the CIE L*a*b* coordinates of the given color, using the D65 white
+/// point.
+pub fn lab(color: Color) -> [f32; 3] {
+	let [r, g, b] = linear_ratios(color);
+	let x = 0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b;
+	let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b;
+	let z = 0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b;
+
+	let f = |t: f32| if t > (6.0f32 / 29.0).powi(3) {
+		t.cbrt()
+	} else {
+		t / (3.0 * (6.0f32 / 29.0).powi(2)) + 4.0 / 29.0
+	};
+	let fx = f(x / WHITE_POINT[0]);
+	let fy = f(y / WHITE_POINT[1]);
+	let fz = f(z / WHITE_POINT[2]);
+	[116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Returns the color with the given CIE L*a*b* coordinates, using the D65 
+/// white point. Colors outside of the sRGB gamut are clamped. Use 
+/// `from_lab_mapped` to bring them into gamut with a `GamutMap` instead.
+pub fn from_lab(lab: [f32; 3]) -> Color {
+	from_lab_mapped(lab, GamutMap::ClampChannel)
+}
+
+/// Returns the color with the given CIE L*a*b* coordinates, using the D65 
+/// white point and the given `GamutMap` to bring colors outside of the sRGB 
+/// gamut into it. The mapping is done on the linear-light ratios.
+pub fn from_lab_mapped(lab: [f32; 3], gamut_map: GamutMap) -> Color {
+	let fy = (lab[0] + 16.0) / 116.0;
+	let fx = fy + lab[1] / 500.0;
+	let fz = fy - lab[2] / 200.0;
+	let f_inv = |t: f32| if t > 6.0 / 29.0 {
+		t.powi(3)
+	} else {
+		3.0 * (6.0f32 / 29.0).powi(2) * (t - 4.0 / 29.0)
+	};
+	let x = f_inv(fx) * WHITE_POINT[0];
+	let y = f_inv(fy) * WHITE_POINT[1];
+	let z = f_inv(fz) * WHITE_POINT[2];
+
+	from_linear_ratios(gamut_map.map([
+		3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+		-0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+		0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+	]))
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// BlendMode
+////////////////////////////////////////////////////////////////////////////////
+/// The method used to combine two colors in a `blend`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum BlendMode {
+	/// Linearly interpolates between the colors.
+	Normal,
+	/// Multiplies the channels of the colors, darkening the result.
+	Multiply,
+	/// Multiplies the inverted channels of the colors, lightening the result.
+	Screen,
+	/// Multiplies dark channels and screens light channels of the base color.
+	Overlay,
+	/// Adds the channels of the colors, saturating at the maximum value.
+	Add,
+}
+
+impl BlendMode {
+	/// Returns the result of blending the given channel ratios.
+	fn channel(self, base: f32, top: f32) -> f32 {
+		match self {
+			BlendMode::Normal	=> top,
+			BlendMode::Multiply	=> base * top,
+			BlendMode::Screen	=> 1.0 - (1.0 - base) * (1.0 - top),
+			BlendMode::Overlay	=> if base < 0.5 {
+					2.0 * base * top
+				} else {
+					1.0 - 2.0 * (1.0 - base) * (1.0 - top)
+				},
+			BlendMode::Add		=> f32::min(base + top, 1.0),
+		}
+	}
+}
+
+impl Default for BlendMode {
+	fn default() -> Self {
+		BlendMode::Normal
+	}
+}
+
+
+/// Blends the color `top` onto the color `base` using the given `BlendMode`. 
+/// The `amount` gives the ratio between the base color and the fully blended 
+/// color, and is clamped between 0 and 1.
+///
+/// # Examples
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::{blend, BlendMode};
+///
+/// let a = Color::new(200, 100, 50);
+/// let b = Color::new(100, 100, 200);
+///
+/// assert_eq!(blend(a, b, BlendMode::Normal, 1.0), b);
+/// assert_eq!(blend(a, b, BlendMode::Multiply, 1.0), Color::new(78, 39, 39));
+/// assert_eq!(blend(a, b, BlendMode::Screen, 1.0), Color::new(222, 161, 211));
+/// assert_eq!(blend(a, b, BlendMode::Overlay, 1.0), Color::new(188, 78, 78));
+/// assert_eq!(blend(a, b, BlendMode::Add, 1.0), Color::new(255, 200, 250));
+/// ```
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::{blend, BlendMode};
+///
+/// let a = Color::new(200, 100, 50);
+/// let b = Color::new(100, 100, 200);
+///
+/// assert_eq!(blend(a, b, BlendMode::Multiply, 0.0), a);
+/// ```
+pub fn blend(base: Color, top: Color, mode: BlendMode, amount: f32) -> Color {
+	let (b, t) = (ratios(base), ratios(top));
+	let mut result = [0.0; 3];
+	for (r, (&b, &t)) in result.iter_mut().zip(b.iter().zip(t.iter())) {
+		*r = lerp_f32(b, mode.channel(b, t), amount);
+	}
+	from_ratios(result)
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ColorMetric
+////////////////////////////////////////////////////////////////////////////////
+/// The method used to measure the distance between two colors.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ColorMetric {
+	/// The euclidean distance between the colors' RGB octets.
+	Euclidean,
+	/// The euclidean distance between the colors' RGB octets, with each channel
+	/// weighted to approximate perceived difference. (The 'redmean' metric.)
+	Weighted,
+}
+
+impl ColorMetric {
+	/// Returns the distance between the given colors.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::Color;
+	/// use palette::colors::ColorMetric;
+	///
+	/// let a = Color::new(0, 0, 0);
+	/// let b = Color::new(3, 4, 0);
+	///
+	/// assert_eq!(ColorMetric::Euclidean.distance(a, b), 5.0);
+	/// ```
+	pub fn distance(self, a: Color, b: Color) -> f32 {
+		let [ar, ag, ab] = octets(a);
+		let [br, bg, bb] = octets(b);
+		let dr = ar as f32 - br as f32;
+		let dg = ag as f32 - bg as f32;
+		let db = ab as f32 - bb as f32;
+
+		match self {
+			ColorMetric::Euclidean => (dr * dr + dg * dg + db * db).sqrt(),
+			ColorMetric::Weighted => {
+				let rmean = (ar as f32 + br as f32) / 2.0;
+				((2.0 + rmean / 256.0) * dr * dr 
+					+ 4.0 * dg * dg 
+					+ (2.0 + (255.0 - rmean) / 256.0) * db * db).sqrt()
+			},
+		}
+	}
+
+	/// Returns the index of the color in `colors` nearest to the given color,
+	/// or None if `colors` is empty.
+	pub fn nearest(self, color: Color, colors: &[Color]) -> Option<usize> {
+		let mut nearest = None;
+		let mut nearest_distance = 0.0;
+		for (i, &candidate) in colors.iter().enumerate() {
+			let distance = self.distance(color, candidate);
+			if nearest.is_none() || distance < nearest_distance {
+				nearest = Some(i);
+				nearest_distance = distance;
+			}
+		}
+		nearest
+	}
+}
+
+impl Default for ColorMetric {
+	fn default() -> Self {
+		ColorMetric::Euclidean
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GamutMap
+////////////////////////////////////////////////////////////////////////////////
+/// The method used to bring out-of-gamut RGB ratios between 0 and 1.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum GamutMap {
+	/// Clamps each channel independently. This may shift the hue of the color.
+	ClampChannel,
+	/// Scales and offsets all channels equally, preserving the hue of the 
+	/// color at the expense of its lightness.
+	PreserveHue,
+	/// Moves the color toward the grey of the same luma until it lies in 
+	/// gamut, preserving the hue and luma at the expense of saturation.
+	DesaturateToGamut,
+}
+
+impl GamutMap {
+	/// Returns the given RGB ratios mapped between 0 and 1.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::colors::{from_lab_mapped, lab, GamutMap};
+	///
+	/// // A saturated pink lying outside of the sRGB gamut.
+	/// let out = [50.0, 100.0, 0.0];
+	/// let hue_error = |gamut_map: GamutMap| {
+	/// 	let mapped = lab(from_lab_mapped(out, gamut_map));
+	/// 	mapped[2].atan2(mapped[1]).to_degrees().abs()
+	/// };
+	///
+	/// assert!(hue_error(GamutMap::PreserveHue) < 1.0);
+	/// assert!(hue_error(GamutMap::ClampChannel) > 3.0);
+	/// ```
+	pub fn map(self, ratios: [f32; 3]) -> [f32; 3] {
+		let [r, g, b] = ratios;
+		let min = f32::min(r, f32::min(g, b));
+		let max = f32::max(r, f32::max(g, b));
+		if min >= 0.0 && max <= 1.0 {
+			return ratios;
+		}
+
+		match self {
+			GamutMap::ClampChannel => [
+				clamped(r, 0.0, 1.0),
+				clamped(g, 0.0, 1.0),
+				clamped(b, 0.0, 1.0),
+			],
+
+			GamutMap::PreserveHue => {
+				let lo = f32::min(min, 0.0);
+				let hi = f32::max(max, 1.0);
+				[(r - lo) / (hi - lo), (g - lo) / (hi - lo), (b - lo) / (hi - lo)]
+			},
+
+			GamutMap::DesaturateToGamut => {
+				let grey = clamped(0.299 * r + 0.587 * g + 0.114 * b, 0.0, 1.0);
+				let mut scale: f32 = 1.0;
+				for &c in &[r, g, b] {
+					if c > 1.0 {
+						scale = scale.min((1.0 - grey) / (c - grey));
+					} else if c < 0.0 {
+						scale = scale.min(grey / (grey - c));
+					}
+				}
+				[
+					clamped(grey + (r - grey) * scale, 0.0, 1.0),
+					clamped(grey + (g - grey) * scale, 0.0, 1.0),
+					clamped(grey + (b - grey) * scale, 0.0, 1.0),
+				]
+			},
+		}
+	}
+}
+
+impl Default for GamutMap {
+	fn default() -> Self {
+		GamutMap::ClampChannel
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ColorSpace
+////////////////////////////////////////////////////////////////////////////////
+/// The color space in which colors are interpolated.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ColorSpace {
+	/// Interpolates the sRGB channels.
+	Rgb,
+	/// Interpolates the linear-light RGB channels.
+	LinearRgb,
+	/// Interpolates hue, saturation, and value. Hues take the shortest path 
+	/// around the color wheel unless another `ArcDirection` is given.
+	Hsv,
+	/// Interpolates hue, saturation, and lightness. Hues take the shortest path 
+	/// around the color wheel unless another `ArcDirection` is given.
+	Hsl,
+	/// Interpolates the CIE L*a*b* coordinates.
+	Lab,
+}
+
+impl ColorSpace {
+	/// Returns the color located at the ratio `t` between the given colors in 
+	/// the color space.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::Color;
+	/// use palette::colors::ColorSpace;
+	///
+	/// let red = Color::new(255, 0, 0);
+	/// let blue = Color::new(0, 0, 255);
+	///
+	/// assert_eq!(ColorSpace::Rgb.interpolate(red, blue, 0.5), 
+	/// 	Color::new(128, 0, 128));
+	/// assert_eq!(ColorSpace::LinearRgb.interpolate(red, blue, 0.5), 
+	/// 	Color::new(188, 0, 188));
+	/// assert_eq!(ColorSpace::Hsl.interpolate(red, blue, 0.5), 
+	/// 	Color::new(255, 0, 255));
+	/// ```
+	pub fn interpolate(self, start: Color, end: Color, t: f32) -> Color {
+		self.interpolate_along(start, end, t, ArcDirection::Shortest)
+	}
+
+	/// Returns the color located at the ratio `t` between the given colors in 
+	/// the color space, moving hues around the color wheel in the given 
+	/// direction. The direction is ignored by color spaces without a hue.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::Color;
+	/// use palette::colors::{hsv, ArcDirection, ColorSpace};
+	///
+	/// let red = Color::new(255, 0, 0);
+	/// let magenta = Color::new(255, 0, 255);
+	///
+	/// let short = ColorSpace::Hsv.interpolate_along(
+	/// 	red, magenta, 0.5, ArcDirection::Shortest);
+	/// let long = ColorSpace::Hsv.interpolate_along(
+	/// 	red, magenta, 0.5, ArcDirection::Clockwise);
+	///
+	/// assert!((hsv(short)[0] - 330.0).abs() < 1.5);
+	/// assert!((hsv(long)[0] - 150.0).abs() < 1.5);
+	/// ```
+	pub fn interpolate_along(
+		self,
+		start: Color,
+		end: Color,
+		t: f32,
+		direction: ArcDirection)
+		-> Color
+	{
+		self.interpolate_with(start, end, t, direction, GamutMap::ClampChannel)
+	}
+
+	/// Returns the color located at the ratio `t` between the given colors in 
+	/// the color space, moving hues around the color wheel in the given 
+	/// direction and using the given `GamutMap` for any interpolated color 
+	/// lying outside of the sRGB gamut.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::Color;
+	/// use palette::colors::{ArcDirection, ColorSpace, GamutMap};
+	///
+	/// let red = Color::new(255, 0, 0);
+	/// let blue = Color::new(0, 0, 255);
+	///
+	/// assert_eq!(
+	/// 	ColorSpace::Lab.interpolate_with(
+	/// 		red, blue, 0.5, ArcDirection::Shortest, GamutMap::ClampChannel),
+	/// 	ColorSpace::Lab.interpolate(red, blue, 0.5));
+	/// ```
+	pub fn interpolate_with(
+		self,
+		start: Color,
+		end: Color,
+		t: f32,
+		direction: ArcDirection,
+		gamut_map: GamutMap)
+		-> Color
+	{
+		let lerp = |a: [f32; 3], b: [f32; 3]| [
+			lerp_f32(a[0], b[0], t),
+			lerp_f32(a[1], b[1], t),
+			lerp_f32(a[2], b[2], t),
+		];
+		match self {
+			ColorSpace::Rgb => blend(start, end, BlendMode::Normal, t),
+
+			ColorSpace::LinearRgb => from_linear_ratios(
+				lerp(linear_ratios(start), linear_ratios(end))),
+
+			ColorSpace::Hsv => {
+				let (a, b) = (hsv(start), hsv(end));
+				let mut mixed = lerp(a, b);
+				mixed[0] = lerp_hue(a[0], b[0], t, direction);
+				from_hsv(mixed)
+			},
+
+			ColorSpace::Hsl => {
+				let (a, b) = (hsl(start), hsl(end));
+				let mut mixed = lerp(a, b);
+				mixed[0] = lerp_hue(a[0], b[0], t, direction);
+				from_hsl_mapped(mixed, gamut_map)
+			},
+
+			ColorSpace::Lab => from_lab_mapped(
+				lerp(lab(start), lab(end)), 
+				gamut_map),
+		}
+	}
+}
+
+impl Default for ColorSpace {
+	fn default() -> Self {
+		ColorSpace::Rgb
+	}
+}
+
+
+/// Returns the hue located at the ratio `t` along the arc between the given 
+/// hues in the given direction, in degrees.
+fn lerp_hue(start: f32, end: f32, t: f32, direction: ArcDirection) -> f32 {
+	// The clockwise distance from the start hue to the end hue.
+	let clockwise = ((end - start) % 360.0 + 360.0) % 360.0;
+	let delta = match direction {
+		ArcDirection::Shortest if clockwise > 180.0 => clockwise - 360.0,
+		ArcDirection::Shortest => clockwise,
+		ArcDirection::Clockwise => clockwise,
+		ArcDirection::Counterclockwise if clockwise > 0.0 => clockwise - 360.0,
+		ArcDirection::Counterclockwise => 0.0,
+	};
+	start + delta * clamped(t, 0.0, 1.0)
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ArcDirection
+////////////////////////////////////////////////////////////////////////////////
+/// The direction in which hues are interpolated around the color wheel.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ArcDirection {
+	/// Takes the shorter arc between the hues.
+	Shortest,
+	/// Moves in the direction of increasing hue, from red toward green.
+	Clockwise,
+	/// Moves in the direction of decreasing hue, from red toward blue.
+	Counterclockwise,
+}
+
+impl Default for ArcDirection {
+	fn default() -> Self {
+		ArcDirection::Shortest
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Named colors
+////////////////////////////////////////////////////////////////////////////////
+/// The CSS named colors and their sRGB octets, in alphabetical order.
+const NAMED_COLORS: [(&str, [u8; 3]); 148] = [
+	("aliceblue", [0xf0, 0xf8, 0xff]),
+	("antiquewhite", [0xfa, 0xeb, 0xd7]),
+	("aqua", [0x00, 0xff, 0xff]),
+	("aquamarine", [0x7f, 0xff, 0xd4]),
+	("azure", [0xf0, 0xff, 0xff]),
+	("beige", [0xf5, 0xf5, 0xdc]),
+	("bisque", [0xff, 0xe4, 0xc4]),
+	("black", [0x00, 0x00, 0x00]),
+	("blanchedalmond", [0xff, 0xeb, 0xcd]),
+	("blue", [0x00, 0x00, 0xff]),
+	("blueviolet", [0x8a, 0x2b, 0xe2]),
+	("brown", [0xa5, 0x2a, 0x2a]),
+	("burlywood", [0xde, 0xb8, 0x87]),
+	("cadetblue", [0x5f, 0x9e, 0xa0]),
+	("chartreuse", [0x7f, 0xff, 0x00]),
+	("chocolate", [0xd2, 0x69, 0x1e]),
+	("coral", [0xff, 0x7f, 0x50]),
+	("cornflowerblue", [0x64, 0x95, 0xed]),
+	("cornsilk", [0xff, 0xf8, 0xdc]),
+	("crimson", [0xdc, 0x14, 0x3c]),
+	("cyan", [0x00, 0xff, 0xff]),
+	("darkblue", [0x00, 0x00, 0x8b]),
+	("darkcyan", [0x00, 0x8b, 0x8b]),
+	("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+	("darkgray", [0xa9, 0xa9, 0xa9]),
+	("darkgreen", [0x00, 0x64, 0x00]),
+	("darkgrey", [0xa9, 0xa9, 0xa9]),
+	("darkkhaki", [0xbd, 0xb7, 0x6b]),
+	("darkmagenta", [0x8b, 0x00, 0x8b]),
+	("darkolivegreen", [0x55, 0x6b, 0x2f]),
+	("darkorange", [0xff, 0x8c, 0x00]),
+	("darkorchid", [0x99, 0x32, 0xcc]),
+	("darkred", [0x8b, 0x00, 0x00]),
+	("darksalmon", [0xe9, 0x96, 0x7a]),
+	("darkseagreen", [0x8f, 0xbc, 0x8f]),
+	("darkslateblue", [0x48, 0x3d, 0x8b]),
+	("darkslategray", [0x2f, 0x4f, 0x4f]),
+	("darkslategrey", [0x2f, 0x4f, 0x4f]),
+	("darkturquoise", [0x00, 0xce, 0xd1]),
+	("darkviolet", [0x94, 0x00, 0xd3]),
+	("deeppink", [0xff, 0x14, 0x93]),
+	("deepskyblue", [0x00, 0xbf, 0xff]),
+	("dimgray", [0x69, 0x69, 0x69]),
+	("dimgrey", [0x69, 0x69, 0x69]),
+	("dodgerblue", [0x1e, 0x90, 0xff]),
+	("firebrick", [0xb2, 0x22, 0x22]),
+	("floralwhite", [0xff, 0xfa, 0xf0]),
+	("forestgreen", [0x22, 0x8b, 0x22]),
+	("fuchsia", [0xff, 0x00, 0xff]),
+	("gainsboro", [0xdc, 0xdc, 0xdc]),
+	("ghostwhite", [0xf8, 0xf8, 0xff]),
+	("gold", [0xff, 0xd7, 0x00]),
+	("goldenrod", [0xda, 0xa5, 0x20]),
+	("gray", [0x80, 0x80, 0x80]),
+	("green", [0x00, 0x80, 0x00]),
+	("greenyellow", [0xad, 0xff, 0x2f]),
+	("grey", [0x80, 0x80, 0x80]),
+	("honeydew", [0xf0, 0xff, 0xf0]),
+	("hotpink", [0xff, 0x69, 0xb4]),
+	("indianred", [0xcd, 0x5c, 0x5c]),
+	("indigo", [0x4b, 0x00, 0x82]),
+	("ivory", [0xff, 0xff, 0xf0]),
+	("khaki", [0xf0, 0xe6, 0x8c]),
+	("lavender", [0xe6, 0xe6, 0xfa]),
+	("lavenderblush", [0xff, 0xf0, 0xf5]),
+	("lawngreen", [0x7c, 0xfc, 0x00]),
+	("lemonchiffon", [0xff, 0xfa, 0xcd]),
+	("lightblue", [0xad, 0xd8, 0xe6]),
+	("lightcoral", [0xf0, 0x80, 0x80]),
+	("lightcyan", [0xe0, 0xff, 0xff]),
+	("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+	("lightgray", [0xd3, 0xd3, 0xd3]),
+	("lightgreen", [0x90, 0xee, 0x90]),
+	("lightgrey", [0xd3, 0xd3, 0xd3]),
+	("lightpink", [0xff, 0xb6, 0xc1]),
+	("lightsalmon", [0xff, 0xa0, 0x7a]),
+	("lightseagreen", [0x20, 0xb2, 0xaa]),
+	("lightskyblue", [0x87, 0xce, 0xfa]),
+	("lightslategray", [0x77, 0x88, 0x99]),
+	("lightslategrey", [0x77, 0x88, 0x99]),
+	("lightsteelblue", [0xb0, 0xc4, 0xde]),
+	("lightyellow", [0xff, 0xff, 0xe0]),
+	("lime", [0x00, 0xff, 0x00]),
+	("limegreen", [0x32, 0xcd, 0x32]),
+	("linen", [0xfa, 0xf0, 0xe6]),
+	("magenta", [0xff, 0x00, 0xff]),
+	("maroon", [0x80, 0x00, 0x00]),
+	("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+	("mediumblue", [0x00, 0x00, 0xcd]),
+	("mediumorchid", [0xba, 0x55, 0xd3]),
+	("mediumpurple", [0x93, 0x70, 0xdb]),
+	("mediumseagreen", [0x3c, 0xb3, 0x71]),
+	("mediumslateblue", [0x7b, 0x68, 0xee]),
+	("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+	("mediumturquoise", [0x48, 0xd1, 0xcc]),
+	("mediumvioletred", [0xc7, 0x15, 0x85]),
+	("midnightblue", [0x19, 0x19, 0x70]),
+	("mintcream", [0xf5, 0xff, 0xfa]),
+	("mistyrose", [0xff, 0xe4, 0xe1]),
+	("moccasin", [0xff, 0xe4, 0xb5]),
+	("navajowhite", [0xff, 0xde, 0xad]),
+	("navy", [0x00, 0x00, 0x80]),
+	("oldlace", [0xfd, 0xf5, 0xe6]),
+	("olive", [0x80, 0x80, 0x00]),
+	("olivedrab", [0x6b, 0x8e, 0x23]),
+	("orange", [0xff, 0xa5, 0x00]),
+	("orangered", [0xff, 0x45, 0x00]),
+	("orchid", [0xda, 0x70, 0xd6]),
+	("palegoldenrod", [0xee, 0xe8, 0xaa]),
+	("palegreen", [0x98, 0xfb, 0x98]),
+	("paleturquoise", [0xaf, 0xee, 0xee]),
+	("palevioletred", [0xdb, 0x70, 0x93]),
+	("papayawhip", [0xff, 0xef, 0xd5]),
+	("peachpuff", [0xff, 0xda, 0xb9]),
+	("peru", [0xcd, 0x85, 0x3f]),
+	("pink", [0xff, 0xc0, 0xcb]),
+	("plum", [0xdd, 0xa0, 0xdd]),
+	("powderblue", [0xb0, 0xe0, 0xe6]),
+	("purple", [0x80, 0x00, 0x80]),
+	("rebeccapurple", [0x66, 0x33, 0x99]),
+	("red", [0xff, 0x00, 0x00]),
+	("rosybrown", [0xbc, 0x8f, 0x8f]),
+	("royalblue", [0x41, 0x69, 0xe1]),
+	("saddlebrown", [0x8b, 0x45, 0x13]),
+	("salmon", [0xfa, 0x80, 0x72]),
+	("sandybrown", [0xf4, 0xa4, 0x60]),
+	("seagreen", [0x2e, 0x8b, 0x57]),
+	("seashell", [0xff, 0xf5, 0xee]),
+	("sienna", [0xa0, 0x52, 0x2d]),
+	("silver", [0xc0, 0xc0, 0xc0]),
+	("skyblue", [0x87, 0xce, 0xeb]),
+	("slateblue", [0x6a, 0x5a, 0xcd]),
+	("slategray", [0x70, 0x80, 0x90]),
+	("slategrey", [0x70, 0x80, 0x90]),
+	("snow", [0xff, 0xfa, 0xfa]),
+	("springgreen", [0x00, 0xff, 0x7f]),
+	("steelblue", [0x46, 0x82, 0xb4]),
+	("tan", [0xd2, 0xb4, 0x8c]),
+	("teal", [0x00, 0x80, 0x80]),
+	("thistle", [0xd8, 0xbf, 0xd8]),
+	("tomato", [0xff, 0x63, 0x47]),
+	("turquoise", [0x40, 0xe0, 0xd0]),
+	("violet", [0xee, 0x82, 0xee]),
+	("wheat", [0xf5, 0xde, 0xb3]),
+	("white", [0xff, 0xff, 0xff]),
+	("whitesmoke", [0xf5, 0xf5, 0xf5]),
+	("yellow", [0xff, 0xff, 0x00]),
+	("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
+
+/// Returns the CSS named color with the given name, ignoring case, or None if
+/// there is no color with that name.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::from_name;
+///
+/// assert_eq!(from_name("rebeccapurple"), Some(Color::new(102, 51, 153)));
+/// assert_eq!(from_name("Red"), Some(Color::new(255, 0, 0)));
+/// assert_eq!(from_name("notacolor"), None);
+/// ```
+pub fn from_name(name: &str) -> Option<Color> {
+	NAMED_COLORS
+		.iter()
+		.find(|&&(n, _)| n.eq_ignore_ascii_case(name))
+		.map(|&(_, [r, g, b])| Color::new(r, g, b))
+}
+
+/// Returns the name of the CSS named color exactly matching the given color, 
+/// or None if there is no such color. Where several names share a color, the
+/// alphabetically first is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::colors::name;
+///
+/// assert_eq!(name(Color::new(102, 51, 153)), Some("rebeccapurple"));
+/// assert_eq!(name(Color::new(255, 0, 0)), Some("red"));
+/// assert_eq!(name(Color::new(0, 255, 255)), Some("aqua"));
+/// assert_eq!(name(Color::new(1, 2, 3)), None);
+/// ```
+pub fn name(color: Color) -> Option<&'static str> {
+	let target = octets(color);
+	NAMED_COLORS
+		.iter()
+		.find(|&&(_, octets)| octets == target)
+		.map(|&(n, _)| n)
+}
@@ -31,27 +31,42 @@
 use address::{
 	Address,
 	Reference,
+	Selection,
 	Page, Line, Column, 
 	PAGE_MAX, LINE_MAX, COLUMN_MAX,
 };
 use cell::Cell;
+use colors::{self, ColorMetric, ColorSpace};
 use expression::Expression;
+use format::{CompatibilityWarning, Format};
+use operation::{selected_colors, PaletteOperation};
 use result::{
 	Error,
 	Result,
 };
+use utilities::clamped;
+use view::PaletteView;
+
+// Non-local imports.
+use color::Color;
 
 // Standard imports.
 use std::collections::{
 	BTreeMap,
 	BTreeSet,
 	HashMap,
+	HashSet,
 };
 use std::rc::Rc;
 use std::fmt;
 use std::mem;
+#[cfg(feature = "timing")]
+use std::time::Duration;
+
 
 
+/// The default number of recent colors kept by the `Data`.
+const DEFAULT_RECENT_CAPACITY: usize = 16;
 
 /// Default function for `prepare_new_page` and `prepare_new_line` triggers.
 #[allow(unused_variables)]
@@ -62,7 +77,7 @@ fn no_op(_: &mut Data, _: &Reference) {}
 // MetaData
 ////////////////////////////////////////////////////////////////////////////////
 /// Provides metadata about palette data.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct MetaData {
 	/// A format-generated label for the item.
 	pub format_label: Option<String>,
@@ -107,6 +122,9 @@ pub struct Data {
 	/// A map assigning references to names.
 	pub names: HashMap<String, Reference>,
 
+	/// A map assigning addresses to named roles.
+	pub roles: HashMap<String, Address>,
+
 	/// A map assigning metadata to references.
 	pub metadata: HashMap<Reference, MetaData>,
 
@@ -119,6 +137,9 @@ pub struct Data {
 	/// The default number of columns in each line.
 	pub default_column_count: Column,
 
+	/// The format whose layout and behaviors the data follows.
+	pub format: Format,
+
 	/// Called before a `Cell` is added to a new page in the palette. The 
 	/// expectation is that this will add the appropriate meta data to the 
 	/// palette. This will be called before the prepare_new_line function is 
@@ -129,6 +150,31 @@ pub struct Data {
 	/// expectation is that this will add the appropriate meta data to the 
 	/// palette.
 	pub prepare_new_line: fn(&mut Data, &Reference),
+
+	/// The time taken to apply the most recent operation.
+	#[cfg(feature = "timing")]
+	pub last_operation_time: Option<Duration>,
+
+	/// The addresses modified by the most recent operation, undo, or redo.
+	pub last_affected: Vec<Address>,
+
+	/// A counter incremented by every applied operation, undo, and redo.
+	generation: u64,
+
+	/// The addresses which are locked against modification by operations.
+	locked: BTreeSet<Address>,
+
+	/// The maximum number of distinct colors allowed in the palette.
+	color_budget: Option<usize>,
+
+	/// The groups which are skipped when searching for free addresses.
+	reserved: Vec<Reference>,
+
+	/// The most recently inserted colors, from oldest to newest.
+	recent_colors: Vec<Color>,
+
+	/// The maximum number of recent colors to keep.
+	recent_capacity: usize,
 }
 
 
@@ -143,12 +189,390 @@ impl Data {
 		self.cells.is_empty()
 	}
 
+	/// Returns an estimate of the number of bytes used by the `Data`, including
+	/// its heap allocations. The overhead of the maps' internal nodes is not 
+	/// included.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let mut usage = dat.memory_usage();
+	///
+	/// for column in 0..64 {
+	/// 	*dat.create_cell(Address::new(0, 0, column)).unwrap().borrow_mut() 
+	/// 		= Expression::Color(Color::new(column, column, column));
+	/// 	assert!(dat.memory_usage() > usage);
+	/// 	usage = dat.memory_usage();
+	/// }
+	/// ```
+	pub fn memory_usage(&self) -> usize {
+		let string = |s: &String| s.capacity();
+
+		let cells = self.cells.len() * (
+			mem::size_of::<(Address, Rc<Cell>)>() + 
+			// The reference counts and the cell itself.
+			2 * mem::size_of::<usize>() + mem::size_of::<Cell>()
+		);
+		let names = self.names.len() * mem::size_of::<(String, Reference)>() +
+			self.names.keys().map(&string).sum::<usize>();
+		let roles = self.roles.len() * mem::size_of::<(String, Address)>() +
+			self.roles.keys().map(&string).sum::<usize>();
+		let metadata = self.metadata.len() 
+			* mem::size_of::<(Reference, MetaData)>() +
+			self.metadata
+				.values()
+				.flat_map(|data| data.name.iter().chain(data.format_label.iter()))
+				.map(&string)
+				.sum::<usize>();
+
+		mem::size_of::<Data>() + cells + names + roles + metadata +
+			self.last_affected.capacity() * mem::size_of::<Address>() +
+			self.locked.len() * mem::size_of::<Address>() +
+			self.reserved.capacity() * mem::size_of::<Reference>() +
+			self.recent_colors.capacity() * mem::size_of::<Color>()
+	}
+
+	/// Returns warnings describing the data which would be lost by exporting
+	/// the data in the given format. Equivalent to 
+	/// `format.export_compatibility(&data)`.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::format::CompatibilityWarning;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// *dat.create_cell(Address::new(0, 14, 0)).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(8, 8, 8));
+	///
+	/// assert_eq!(dat.export_compatibility(Format::Default), vec![]);
+	/// assert_eq!(dat.export_compatibility(Format::Zpl), vec![
+	/// 	CompatibilityWarning::OutOfBounds(Address::new(0, 14, 0)),
+	/// ]);
+	/// ```
+	#[inline]
+	pub fn export_compatibility(&self, format: Format) 
+		-> Vec<CompatibilityWarning>
+	{
+		format.export_compatibility(self)
+	}
+
+	/// Returns a CSS `linear-gradient()` at the given angle, in degrees, whose 
+	/// stops are the colors in the given selection in address order, evenly 
+	/// spaced. Returns None if the selection contains no colors.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::address::Selection;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// for (column, &v) in [0, 128, 255].iter().enumerate() {
+	/// 	*dat.create_cell(Address::new(0, 0, column as u8)).unwrap()
+	/// 		.borrow_mut() = Expression::Color(Color::new(v, v, v));
+	/// }
+	///
+	/// assert_eq!(
+	/// 	dat.to_css_gradient(&Selection::line(0, 0), 90.0), 
+	/// 	Some("linear-gradient(90deg, #000000 0%, #808080 50%, #ffffff 100%)"
+	/// 		.to_string()));
+	/// assert_eq!(dat.to_css_gradient(&Selection::line(0, 1), 90.0), None);
+	/// ```
+	pub fn to_css_gradient(&self, selection: &Selection, angle: f32) 
+		-> Option<String>
+	{
+		let mut stop_colors: Vec<Color> = selected_colors(self, selection)
+			.into_iter()
+			.map(|(_, color)| color)
+			.collect();
+		match stop_colors.len() {
+			0 => return None,
+			1 => { let color = stop_colors[0]; stop_colors.push(color); },
+			_ => (),
+		}
+
+		let last = (stop_colors.len() - 1) as f32;
+		let stops: Vec<String> = stop_colors
+			.iter()
+			.enumerate()
+			.map(|(i, &color)| {
+				let [r, g, b] = colors::octets(color);
+				let percent = (i as f32 * 10000.0 / last).round() / 100.0;
+				format!("#{:02x}{:02x}{:02x} {}%", r, g, b, percent)
+			})
+			.collect();
+
+		Some(format!("linear-gradient({}deg, {})", angle, stops.join(", ")))
+	}
+
+	/// Returns the wall-clock time taken to apply the most recent operation, or
+	/// None if no operation has been applied.
+	#[cfg(feature = "timing")]
+	pub fn last_operation_time(&self) -> Option<Duration> {
+		self.last_operation_time
+	}
+
+	/// Returns the number of operations, undos, and redos which have modified 
+	/// the `Data`. Any cache of resolved colors made at an earlier generation
+	/// may be stale.
+	pub fn generation(&self) -> u64 {
+		self.generation
+	}
+
+	/// Increments the generation counter.
+	pub(crate) fn increment_generation(&mut self) {
+		self.generation += 1;
+	}
+
+	/// Replaces the `Data` with the given snapshot, advancing the generation 
+	/// counter past its current value.
+	pub(crate) fn restore(&mut self, snapshot: Data) {
+		let generation = self.generation;
+		*self = snapshot;
+		self.generation = generation + 1;
+	}
+
 	/// Returns a reference to the cell located at the given address, or None if
 	/// the address is invalid or empty.
 	pub fn cell(&self, address: Address) -> Option<Rc<Cell>> {
 		self.cells.get(&address).cloned()
 	}
 
+	/// Calls the given function with mutable access to the `Expression` of the 
+	/// cell at the given address, returning its result. The change is not 
+	/// recorded in the palette's history, but it does advance the generation.
+	///
+	/// # Errors
+	///
+	/// Returns an `EmptyAddress` error if there is no cell at the address, a 
+	/// `SlotLocked` error if the address is locked, and a `CellBorrowed` error
+	/// if the cell is already borrowed elsewhere.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// *dat.create_cell(Address::new(0, 0, 0)).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(10, 10, 10));
+	///
+	/// let old = dat.with_cell_mut(Address::new(0, 0, 0), |expr| {
+	/// 	let old = expr.color();
+	/// 	*expr = Expression::Color(Color::new(90, 90, 90));
+	/// 	old
+	/// }).unwrap();
+	///
+	/// assert_eq!(old, Some(Color::new(10, 10, 10)));
+	/// assert_eq!(
+	/// 	dat.cell(Address::new(0, 0, 0)).and_then(|cell| cell.color()), 
+	/// 	Some(Color::new(90, 90, 90)));
+	/// assert!(dat.with_cell_mut(Address::new(0, 0, 1), |_| ()).is_err());
+	/// ```
+	pub fn with_cell_mut<F, R>(&mut self, address: Address, f: F) -> Result<R>
+		where F: FnOnce(&mut Expression) -> R
+	{
+		let cell = self.cell(address).ok_or(Error::EmptyAddress(address))?;
+		if self.is_locked(address) {
+			return Err(Error::SlotLocked(address));
+		}
+		let result = {
+			let mut expr = cell
+				.try_borrow_mut()
+				.map_err(|_| Error::CellBorrowed(address))?;
+			f(&mut *expr)
+		};
+		self.increment_generation();
+		Ok(result)
+	}
+
+	/// Returns the color lying at the ratio `t` between the colors of the 
+	/// cells at the given addresses, or None if either cell has no color. The
+	/// ratio is clamped between 0 and 1. The colors are interpolated in the 
+	/// same way as the default `InsertRamp`.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::data::Data;
+	/// use palette::{Address, Color, Expression};
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let (a, b) = (Address::new(0, 0, 0), Address::new(0, 0, 1));
+	/// *dat.create_cell(a).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(0, 0, 0));
+	/// *dat.create_cell(b).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(100, 200, 50));
+	///
+	/// assert_eq!(dat.sample_ramp(a, b, 0.0), Some(Color::new(0, 0, 0)));
+	/// assert_eq!(dat.sample_ramp(a, b, 0.5), Some(Color::new(50, 100, 25)));
+	/// assert_eq!(dat.sample_ramp(a, b, 1.0), Some(Color::new(100, 200, 50)));
+	/// ```
+	pub fn sample_ramp(&self, from: Address, to: Address, t: f32) 
+		-> Option<Color>
+	{
+		let start = self.cell(from).and_then(|cell| cell.color());
+		let end = self.cell(to).and_then(|cell| cell.color());
+		match (start, end) {
+			(Some(start), Some(end)) => Some(
+				ColorSpace::Rgb.interpolate(start, end, clamped(t, 0.0, 1.0))),
+			_	=> None,
+		}
+	}
+
+	/// Returns an immutable `PaletteView` holding the resolved colors of every
+	/// `Cell` in the `Data`.
+	pub fn to_view(&self) -> PaletteView {
+		PaletteView::new(self.cells
+			.iter()
+			.map(|(&address, cell)| (address, cell.color())))
+	}
+
+	/// Returns the table of resolved colors in the `Data`, in address order, 
+	/// along with a function which returns the index of the nearest color in
+	/// the table for any given color, as measured by the given `ColorMetric`.
+	/// The function returns 0 if the table is empty.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::data::Data;
+	/// use palette::colors::ColorMetric;
+	/// use palette::{Address, Color, Expression};
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let colors = [Color::new(0, 0, 0), Color::new(250, 10, 10)];
+	/// for (i, &color) in colors.iter().enumerate() {
+	/// 	*dat.create_cell(Address::new(0, 0, i as u8)).unwrap().borrow_mut()
+	/// 		= Expression::Color(color);
+	/// }
+	///
+	/// let (table, indexer) = dat.build_indexer(ColorMetric::Euclidean);
+	///
+	/// assert_eq!(&table[..], &colors[..]);
+	/// assert_eq!(indexer(colors[0]), 0);
+	/// assert_eq!(indexer(colors[1]), 1);
+	/// assert_eq!(indexer(Color::new(200, 30, 20)), 1);
+	/// ```
+	pub fn build_indexer(&self, metric: ColorMetric) 
+		-> (Vec<Color>, impl Fn(Color) -> usize)
+	{
+		let table: Vec<Color> = self.cells
+			.values()
+			.filter_map(|cell| cell.color())
+			.collect();
+		let lookup = table.clone();
+
+		(table, move |color| metric.nearest(color, &lookup).unwrap_or(0))
+	}
+
+	/// Applies the given operation to a copy of the `Data` and returns the 
+	/// colors before and after the operation for each address whose color or
+	/// occupancy was changed. The `Data` itself is left unchanged.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::data::Data;
+	/// use palette::operation::DeleteCell;
+	/// use palette::{Address, Color, Expression};
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let address = Address::new(0, 0, 0);
+	/// *dat.create_cell(address).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(12, 50, 78));
+	///
+	/// let changes = dat.preview_apply(&mut DeleteCell::new(address)).unwrap();
+	///
+	/// assert_eq!(changes[&address], (Some(Color::new(12, 50, 78)), None));
+	/// assert_eq!(dat.len(), 1);
+	/// ```
+	pub fn preview_apply(&self, operation: &mut PaletteOperation)
+		-> Result<HashMap<Address, (Option<Color>, Option<Color>)>>
+	{
+		let mut preview = self.clone();
+		operation.apply(&mut preview)?;
+
+		let addresses: BTreeSet<Address> = self.cells.keys()
+			.chain(preview.cells.keys())
+			.cloned()
+			.collect();
+
+		let mut changes = HashMap::new();
+		for address in addresses {
+			let before = self.cell(address);
+			let after = preview.cell(address);
+			let colors = (
+				before.as_ref().and_then(|cell| cell.color()),
+				after.as_ref().and_then(|cell| cell.color()),
+			);
+			if before.is_some() != after.is_some() || colors.0 != colors.1 {
+				changes.insert(address, colors);
+			}
+		}
+		Ok(changes)
+	}
+
+	/// Returns whether the `Data` resolves to the same collection of colors as
+	/// the given `Data`, regardless of their addresses. Repeated colors must
+	/// occur the same number of times in each.
+	pub fn color_set_eq(&self, other: &Data) -> bool {
+		let color_set = |data: &Data| {
+			let mut set: Vec<[u8; 3]> = data.cells
+				.values()
+				.filter_map(|cell| cell.color())
+				.map(colors::octets)
+				.collect();
+			set.sort();
+			set
+		};
+		color_set(self) == color_set(other)
+	}
+
+	/// Returns the addresses of the cells whose colors satisfy the given 
+	/// predicate, in address order.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::data::Data;
+	/// use palette::colors;
+	/// use palette::{Address, Color, Expression};
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let values = [10, 200, 40, 250];
+	/// for (i, &v) in values.iter().enumerate() {
+	/// 	*dat.create_cell(Address::new(0, 0, i as u8)).unwrap().borrow_mut()
+	/// 		= Expression::Color(Color::new(v, v, v));
+	/// }
+	///
+	/// let dark = dat.find(|_, color| {
+	/// 	let [r, g, b] = colors::octets(color);
+	/// 	(r as u32 + g as u32 + b as u32) / 3 < 128
+	/// });
+	///
+	/// assert_eq!(dark, vec![Address::new(0, 0, 0), Address::new(0, 0, 2)]);
+	/// ```
+	pub fn find<F>(&self, predicate: F) -> Vec<Address>
+		where F: Fn(Address, Color) -> bool
+	{
+		self.cells
+			.iter()
+			.filter_map(|(&address, cell)| cell.color().map(|c| (address, c)))
+			.filter(|&(address, color)| predicate(address, color))
+			.map(|(address, _)| address)
+			.collect()
+	}
+
 	/// Returns a reference to the cell located at the given address. If 
 	/// the address is empty, a new cell will be created an a weak reference 
 	/// will be returned. Returns None if the address is invalid.
@@ -176,6 +600,190 @@ impl Data {
 		}
 	}
 
+	/// Returns whether the given address is locked against modification.
+	pub fn is_locked(&self, address: Address) -> bool {
+		self.locked.contains(&address)
+	}
+
+	/// Locks or unlocks the given address. Operations will fail with a 
+	/// `SlotLocked` error when modifying a locked address, except for those 
+	/// which transform a selection, which will skip it. Undo and redo are not 
+	/// affected by locks.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # extern crate interval;
+	/// # extern crate palette;
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::operation::*;
+	/// use palette::address::Selection;
+	/// use palette::result::Error;
+	/// use interval::Interval;
+	/// 
+	/// # fn main() {
+	/// let mut dat: Data = Default::default();
+	/// let locked = Address::new(0, 0, 0);
+	/// let unlocked = Address::new(0, 0, 1);
+	/// for &address in [locked, unlocked].iter() {
+	/// 	*dat.create_cell(address).unwrap().borrow_mut() 
+	/// 		= Expression::Color(Color::new(100, 100, 100));
+	/// }
+	/// dat.set_locked(locked, true);
+	///
+	/// let selection = Selection::new(vec![Interval::closed(locked, unlocked)]);
+	/// Recolor::new(selection, 0.0, 1.0).apply(&mut dat).unwrap();
+	///
+	/// let color = |address| dat.cell(address).and_then(|cell| cell.color());
+	/// assert_eq!(color(locked), Some(Color::new(100, 100, 100)));
+	/// assert!(color(unlocked) != Some(Color::new(100, 100, 100)));
+	///
+	/// match DeleteCell::new(locked).apply(&mut dat) {
+	/// 	Err(Error::SlotLocked(address)) => assert_eq!(address, locked),
+	/// 	_ => panic!("expected SlotLocked error"),
+	/// }
+	/// # }
+	/// ```
+	pub fn set_locked(&mut self, address: Address, locked: bool) {
+		if locked {
+			self.locked.insert(address);
+		} else {
+			self.locked.remove(&address);
+		}
+	}
+
+	/// Returns the most recently inserted colors, from oldest to newest.
+	pub fn recent_colors(&self) -> &[Color] {
+		&self.recent_colors
+	}
+
+	/// Returns the maximum number of recent colors to keep.
+	pub fn recent_capacity(&self) -> usize {
+		self.recent_capacity
+	}
+
+	/// Sets the maximum number of recent colors to keep, discarding the oldest
+	/// colors if there are too many.
+	pub fn set_recent_capacity(&mut self, capacity: usize) {
+		self.recent_capacity = capacity;
+		let excess = self.recent_colors.len().saturating_sub(capacity);
+		self.recent_colors.drain(0..excess);
+	}
+
+	/// Records the given color as the most recently inserted color. Repeated
+	/// colors are only recorded once.
+	pub(crate) fn push_recent_color(&mut self, color: Color) {
+		if self.recent_colors.last() == Some(&color) { return; }
+		self.recent_colors.push(color);
+		let excess = self.recent_colors.len().saturating_sub(self.recent_capacity);
+		self.recent_colors.drain(0..excess);
+	}
+
+	/// Returns whether the given address lies in a reserved group.
+	pub fn is_reserved(&self, address: Address) -> bool {
+		self.reserved.iter().any(|group| group.contains(&address))
+	}
+
+	/// Reserves the given group. Reserved addresses are skipped when searching
+	/// for free addresses, so colors will only be placed in them explicitly or
+	/// by overwriting.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::operation::*;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// dat.reserve(Reference::page_of(&Address::new(0, 0, 0)));
+	///
+	/// InsertColor::new(Color::new(12, 50, 78)).apply(&mut dat).unwrap();
+	/// assert!(dat.cell(Address::new(1, 0, 0)).is_some());
+	/// assert!(dat.cell(Address::new(0, 0, 0)).is_none());
+	///
+	/// dat.unreserve(&Reference::page_of(&Address::new(0, 0, 0)));
+	/// InsertColor::new(Color::new(12, 50, 78)).apply(&mut dat).unwrap();
+	/// assert!(dat.cell(Address::new(0, 0, 0)).is_some());
+	/// ```
+	pub fn reserve(&mut self, group: Reference) {
+		if !self.reserved.contains(&group) {
+			self.reserved.push(group);
+		}
+	}
+
+	/// Returns the reserved groups.
+	pub fn reserved(&self) -> &[Reference] {
+		&self.reserved
+	}
+
+	/// Removes the reservation of the given group.
+	pub fn unreserve(&mut self, group: &Reference) {
+		self.reserved.retain(|reserved| reserved != group);
+	}
+
+	/// Returns the maximum number of distinct colors allowed in the palette, or
+	/// None if there is no limit.
+	pub fn color_budget(&self) -> Option<usize> {
+		self.color_budget
+	}
+
+	/// Sets the maximum number of distinct colors allowed in the palette. 
+	/// Operations will fail with a `BudgetExceeded` error when storing a color
+	/// which would exceed the budget. Colors equal to an existing color do not
+	/// count against the budget.
+	pub fn set_color_budget(&mut self, budget: Option<usize>) {
+		self.color_budget = budget;
+	}
+
+	/// Returns whether storing the given color at the given address would keep
+	/// the number of distinct colors within the color budget.
+	pub fn within_color_budget(&self, address: Address, color: Color) -> bool {
+		let budget = match self.color_budget {
+			Some(budget) => budget,
+			None => return true,
+		};
+
+		let target = colors::octets(color);
+		let mut distinct = HashSet::new();
+		for (&other, cell) in &self.cells {
+			if other == address { continue; }
+			if let Some(c) = cell.color() {
+				let octets = colors::octets(c);
+				if octets == target { return true; }
+				distinct.insert(octets);
+			}
+		}
+		distinct.len() < budget
+	}
+
+	/// Returns the cell located at the given address, creating an empty cell 
+	/// if the address is empty. Returns an error if the address is invalid.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::data::Data;
+	/// use palette::{Address, Color, Expression};
+	/// use std::rc::Rc;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let first = dat.get_or_create_cell(Address::new(1, 1, 1)).unwrap();
+	/// *first.borrow_mut() = Expression::Color(Color::new(1, 2, 3));
+	/// let second = dat.get_or_create_cell(Address::new(1, 1, 1)).unwrap();
+	///
+	/// assert!(Rc::ptr_eq(&first, &second));
+	/// assert_eq!(dat.len(), 1);
+	/// ```
+	pub fn get_or_create_cell(&mut self, address: Address) 
+		-> Result<Rc<Cell>> 
+	{
+		match self.cell(address) {
+			Some(cell) => Ok(cell),
+			None => self.create_cell(address),
+		}
+	}
 
 	/// Removes the expression at the given address from the palette. Returns
 	/// the removed expression, or an error if the given address is empty.
@@ -253,8 +861,36 @@ impl Data {
 			.name = Some(name.into());
 	}
 
-	/// Returns the next free address after the given address. And error will be
-	/// returned if there are no more free addresses.
+	/// Returns the address assigned to the given role, or None if the role is 
+	/// not assigned.
+	pub fn role(&self, role: &str) -> Option<Address> {
+		self.roles.get(role).cloned()
+	}
+
+	/// Returns the color of the cell assigned to the given role, or None if 
+	/// the role is not assigned or the cell is empty.
+	pub fn role_color(&self, role: &str) -> Option<Color> {
+		self.role(role)
+			.and_then(|address| self.cell(address))
+			.and_then(|cell| cell.color())
+	}
+
+	/// Assigns the given role to the given address, replacing any previous 
+	/// assignment.
+	pub fn set_role<S>(&mut self, role: S, address: Address) 
+		where S: Into<String>
+	{
+		self.roles.insert(role.into(), address);
+	}
+
+	/// Removes the given role, returning its address.
+	pub fn remove_role(&mut self, role: &str) -> Option<Address> {
+		self.roles.remove(role)
+	}
+
+	/// Returns the next free address after the given address, skipping reserved
+	/// addresses. And error will be returned if there are no more free 
+	/// addresses.
 	pub fn first_free_address_after(
 		&mut self, 
 		starting_address: Address) 
@@ -264,7 +900,7 @@ impl Data {
 		self.prepare_address(address)?;
 
 		// Loop until we don't see a color.
-		while self.cells
+		while self.is_reserved(address) || self.cells
 			.get(&address)
 			.and_then(|s| s.color())
 			.is_some() 
@@ -283,9 +919,78 @@ impl Data {
 		Ok(address)
 	}
 
-	/// Calls the prepare_new_page function and returns the current line count 
-	/// for the given group.
-	fn line_count(&mut self, group: &Reference) -> Line {
+	/// Returns the first unreserved address within the given group which does 
+	/// not contain a color, or None if the group is full.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::data::Data;
+	/// use palette::{Address, Color, Expression, Reference};
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// *dat.create_cell(Address::new(2, 0, 0)).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(12, 50, 78));
+	///
+	/// let page = Reference::page_of(&Address::new(2, 0, 0));
+	/// assert_eq!(dat.first_free_address_in(&page), Some(Address::new(2, 0, 1)));
+	/// ```
+	///
+	/// Reserved addresses are skipped:
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::operation::*;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// dat.reserve(Reference::line_of(&Address::new(2, 0, 0)));
+	///
+	/// let page = Reference::page_of(&Address::new(2, 0, 0));
+	/// assert_eq!(dat.first_free_address_in(&page), Some(Address::new(2, 1, 0)));
+	///
+	/// InsertColor::new(Color::new(12, 50, 78)).within(page).apply(&mut dat)
+	/// 	.unwrap();
+	/// assert!(dat.cell(Address::new(2, 1, 0)).is_some());
+	/// assert!(dat.cell(Address::new(2, 0, 0)).is_none());
+	/// ```
+	pub fn first_free_address_in(&self, group: &Reference) -> Option<Address> {
+		let pages = match group.page() {
+			Ok(page) => page..page.saturating_add(1),
+			Err(_) => 0..self.maximum_page_count,
+		};
+
+		for page in pages {
+			let page_group = Reference::page_of(&Address::new(page, 0, 0));
+			let lines = match group.line() {
+				Ok(line) => line..line.saturating_add(1),
+				Err(_) => 0..self.line_count(&page_group),
+			};
+
+			for line in lines {
+				let line_group = Reference::line_of(&Address::new(page, line, 0));
+				let columns = match group.column() {
+					Ok(column) => column..column.saturating_add(1),
+					Err(_) => 0..self.column_count(&line_group),
+				};
+
+				for column in columns {
+					let address = Address::new(page, line, column);
+					if !self.is_reserved(address) && self.cells
+						.get(&address)
+						.and_then(|s| s.color())
+						.is_none()
+					{
+						return Some(address);
+					}
+				}
+			}
+		}
+		None
+	}
+
+	/// Returns the current line count for the given group.
+	pub fn line_count(&self, group: &Reference) -> Line {
 		self.metadata
 			.get(group)
 			.map_or(self.default_line_count, |meta| meta.line_count)
@@ -299,9 +1004,8 @@ impl Data {
 			.line_count = line_count;
 	}
 
-	/// Calls the prepare_new_line function and returns the current column count 
-	/// for the given group.
-	fn column_count(&mut self, group: &Reference) -> Column {
+	/// Returns the current column count for the given group.
+	pub fn column_count(&self, group: &Reference) -> Column {
 		self.metadata
 			.get(group)
 			.map_or(self.default_column_count, |meta| meta.column_count)
@@ -319,9 +1023,27 @@ impl Data {
 			.column_count = column_count;
 	}
 
-	/// Returns whether the give address lies within the bounds defined by the 
+	/// Returns whether the given address lies within the bounds defined by the 
 	/// wrapping and max page settings for the palette.
-	fn check_address(&mut self, address: Address) -> bool {
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::operation::*;
+	///
+	/// let mut dat: Data = Default::default();
+	/// dat.maximum_page_count = 4;
+	///
+	/// assert!(dat.valid_address(Address::new(3, 0, 0)));
+	/// assert!(!dat.valid_address(Address::new(4, 0, 0)));
+	///
+	/// let mut pal = Palette::new("Example", Format::Zpl, false);
+	/// assert!(pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0))
+	/// 	.located_at(Address::new(600, 0, 0)))).is_err());
+	/// ```
+	pub fn valid_address(&self, address: Address) -> bool {
 		address.page < self.maximum_page_count &&
 		address.line < self.line_count(&Reference::page_of(&address)) &&
 		address.column < self.column_count(&Reference::line_of(&address))
@@ -330,7 +1052,7 @@ impl Data {
 	/// Prepares an address by calling the palette format's metadata functions.
 	/// This function must be called on any address that is first in a new line
 	/// in order to ensure the palette wraps properly.
-	fn prepare_address(&mut self, address: Address) -> Result<()> {
+	pub(crate) fn prepare_address(&mut self, address: Address) -> Result<()> {
 		let default_line_count = self.default_line_count;
 		let default_column_count = self.default_column_count;
 		let page_group = Reference::page_of(&address);
@@ -346,7 +1068,7 @@ impl Data {
 			(self.prepare_new_line)(self, &line_group);
 		}
 		
-		if self.check_address(address) {
+		if self.valid_address(address) {
 			Ok(())
 		} else {
 			Err(Error::InvalidAddress(address))
@@ -355,9 +1077,9 @@ impl Data {
 
 	/// Retrieves n target addresses after starting_address from the palette. If 
 	/// overwrite is true, the addresses may potentially contain expressions. 
-	/// Otherwise, they will be empty. Addresses provided in the exclude list 
-	/// will be skipped. Returns an error if more targets are requested than are
-	/// available in the palette.
+	/// Otherwise, they will be empty and not reserved. Addresses provided in 
+	/// the exclude list will be skipped. Returns an error if more targets are 
+	/// requested than are available in the palette.
 	pub fn find_targets(
 		&mut self, 
 		n: usize, 
@@ -391,6 +1113,7 @@ impl Data {
 
 			// Check if the starting address is empty.
 			if next == starting_address && 
+				!self.is_reserved(next) &&
 				self.cells.get(&next).and_then(|s| s.color()).is_none() &&
 				!exclude.clone().map_or(false, |ex| ex.contains(&next))
 			{
@@ -405,6 +1128,9 @@ impl Data {
 					self.column_count(&Reference::line_of(&next)),
 				);
 				next = self.first_free_address_after(next)?;
+				if targets.contains(&next) {
+					return Err(Error::MaxCellLimitExceeded);
+				}
 				// Add the target if it's not in the exclude list.
 				if !exclude.clone().map_or(false, |ex| ex.contains(&next)) {
 					targets.insert(next);
@@ -483,17 +1209,59 @@ impl fmt::Display for Data {
 }
 
 
+// Clones the `Data`, duplicating each `Cell` rather than sharing it.
+impl Clone for Data {
+	fn clone(&self) -> Self {
+		Data {
+			cells: self.cells
+				.iter()
+				.map(|(&address, cell)| (address, Rc::new((**cell).clone())))
+				.collect(),
+			names: self.names.clone(),
+			roles: self.roles.clone(),
+			metadata: self.metadata.clone(),
+			maximum_page_count: self.maximum_page_count,
+			default_line_count: self.default_line_count,
+			default_column_count: self.default_column_count,
+			format: self.format,
+			prepare_new_page: self.prepare_new_page,
+			prepare_new_line: self.prepare_new_line,
+			#[cfg(feature = "timing")]
+			last_operation_time: self.last_operation_time,
+			last_affected: self.last_affected.clone(),
+			generation: self.generation,
+			locked: self.locked.clone(),
+			color_budget: self.color_budget,
+			reserved: self.reserved.clone(),
+			recent_colors: self.recent_colors.clone(),
+			recent_capacity: self.recent_capacity,
+		}
+	}
+}
+
+
 impl Default for Data {
 	fn default() -> Self {
 		Data {
 			cells: BTreeMap::new(),
 			names: HashMap::new(),
+			roles: HashMap::new(),
 			metadata: HashMap::new(),
 			maximum_page_count: PAGE_MAX,
 			default_line_count: LINE_MAX,
 			default_column_count: COLUMN_MAX,
+			format: Format::Default,
 			prepare_new_page: no_op,
 			prepare_new_line: no_op,
+			#[cfg(feature = "timing")]
+			last_operation_time: None,
+			last_affected: Vec::new(),
+			generation: 0,
+			locked: BTreeSet::new(),
+			color_budget: None,
+			reserved: Vec::new(),
+			recent_colors: Vec::new(),
+			recent_capacity: DEFAULT_RECENT_CAPACITY,
 		}
 	}
 }
\ No newline at end of file
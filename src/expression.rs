@@ -40,7 +40,7 @@ use std::fmt;
 // Expression
 ////////////////////////////////////////////////////////////////////////////////
 /// An AST in the color-expression grammar.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum Expression {
 	/// An empty expression.
 	Empty,
@@ -52,7 +52,10 @@ pub enum Expression {
 impl Expression {
 	/// Returns the `Color` generated by the expression.
 	pub fn color(&self) -> Option<Color> {
-		None
+		match *self {
+			Expression::Empty			=> None,
+			Expression::Color(color)	=> Some(color),
+		}
 	}
 }
 
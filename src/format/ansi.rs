@@ -0,0 +1,133 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides a terminal preview of palette data using ANSI escape sequences.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use colors;
+use data::Data;
+use result::Result;
+
+// Non-local imports.
+use color::Color;
+
+// Standard imports.
+use std::io;
+
+
+/// The channel levels of the xterm-256 color cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+
+/// Returns the xterm-256 color code nearest to the given color.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::Color;
+/// use palette::format::ansi::xterm_code;
+///
+/// assert_eq!(xterm_code(Color::new(255, 0, 0)), 196);
+/// assert_eq!(xterm_code(Color::new(128, 128, 128)), 244);
+/// ```
+pub fn xterm_code(color: Color) -> u8 {
+	let [r, g, b] = colors::octets(color);
+	let distance = |c: [u8; 3]| {
+		let dr = r as i32 - c[0] as i32;
+		let dg = g as i32 - c[1] as i32;
+		let db = b as i32 - c[2] as i32;
+		dr * dr + dg * dg + db * db
+	};
+	let nearest_level = |v: u8| {
+		(0..6).min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+			.unwrap_or(0)
+	};
+
+	// Nearest color in the 6x6x6 cube.
+	let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+	let cube = [CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]];
+	let cube_code = 16 + 36 * ri + 6 * gi + bi;
+
+	// Nearest color in the greyscale ramp.
+	let mean = (r as u32 + g as u32 + b as u32) / 3;
+	let grey_index = if mean < 8 { 0 } else { ((mean - 8) / 10).min(23) };
+	let grey = (8 + 10 * grey_index) as u8;
+	let grey_code = 232 + grey_index as usize;
+
+	if distance([grey, grey, grey]) < distance(cube) {
+		grey_code as u8
+	} else {
+		cube_code as u8
+	}
+}
+
+
+/// Writes a preview of the given data to the given buffer, drawing each cell as
+/// a colored block. Each line of the palette is written on its own row. If 
+/// `truecolor` is false, the nearest xterm-256 colors are used instead of 
+/// 24-bit colors.
+pub fn write_ansi<W>(data: &Data, out_buf: &mut W, truecolor: bool) 
+	-> Result<()>
+	where W: io::Write
+{
+	let mut current_line = None;
+	let mut next_column = 0;
+	for (&address, cell) in &data.cells {
+		// Start a new row for each line.
+		if current_line != Some((address.page, address.line)) {
+			if current_line.is_some() {
+				writeln!(out_buf)?;
+			}
+			if current_line.map_or(true, |(page, _)| page != address.page) {
+				writeln!(out_buf, "Page {}", address.page)?;
+			}
+			current_line = Some((address.page, address.line));
+			next_column = 0;
+		}
+
+		// Pad empty columns.
+		for _ in next_column..address.column as usize {
+			write!(out_buf, "  ")?;
+		}
+		next_column = address.column as usize + 1;
+
+		match cell.color() {
+			Some(color) if truecolor => {
+				let [r, g, b] = colors::octets(color);
+				write!(out_buf, "\x1b[48;2;{};{};{}m  \x1b[0m", r, g, b)?;
+			},
+			Some(color) => {
+				write!(out_buf, "\x1b[48;5;{}m  \x1b[0m", xterm_code(color))?;
+			},
+			None => write!(out_buf, "  ")?,
+		}
+	}
+
+	if current_line.is_some() {
+		writeln!(out_buf)?;
+	}
+	Ok(())
+}
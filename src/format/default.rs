@@ -28,8 +28,12 @@
 
 // Local imports.
 use ::Palette;
-use operation::PaletteOperation;
-use result::Result;
+use address::Selection;
+use operation::{HistoryEntry, PaletteOperation};
+use result::{Error, Result};
+
+#[cfg(feature = "timing")]
+use std::time::Instant;
 
 
 
@@ -42,11 +46,26 @@ pub fn apply_operation(
 	let data = &mut palette.data;
 	let history = &mut palette.operation_history;
 	// Apply operation.
-	let entry = operation.apply(data)?;
+	#[cfg(feature = "timing")]
+	let start = Instant::now();
+	let entry = match operation.apply(data) {
+		Ok(entry) => entry,
+		Err(e) => {
+			// A failed operation may have written and reverted cells, so any 
+			// cached views of the data are invalidated all the same.
+			data.increment_generation();
+			return Err(e);
+		},
+	};
+	data.increment_generation();
+	data.last_affected = entry.affected();
+	#[cfg(feature = "timing")]
+	{
+		data.last_operation_time = Some(start.elapsed());
+	}
 	// Add history entry if history is enabled.
 	if let Some(ref mut history) = *history {
-		history.undo_entries.push(entry);
-		history.redo_entries.clear();
+		history.record(entry);
 	}
 	Ok(())
 }
@@ -61,8 +80,51 @@ pub fn undo(palette: &mut Palette) -> Result<()> {
 		// Check for history entry.
 		if let Some(mut entry) = history.undo_entries.pop() {
 			let redo = entry.undo.apply(data)?;
-			history.redo_entries.push(redo);
+			data.increment_generation();
+			data.last_affected = redo.affected();
+			// Keep the original operation's info for the redo entry.
+			history.redo_entries.push(HistoryEntry {
+				info: entry.info,
+				undo: redo.undo,
+			});
+		}
+		history.commit();
+		Ok(())
+	} else {
+		panic!("undo not supported")
+	}
+}
+
+
+/// Reverses the changes made by the most recently applied operation which lie
+/// within the given selection. The remaining changes are kept on the undo 
+/// stack.
+pub fn undo_within(palette: &mut Palette, selection: &Selection) -> Result<()> {
+	let data = &mut palette.data;
+	let history = &mut palette.operation_history;
+	// Check if history is enable.
+	if let Some(ref mut history) = *history {
+		// Check for history entry.
+		if let Some(mut entry) = history.undo_entries.pop() {
+			if !entry.undo.restrict(Some(selection)) {
+				entry.undo.restrict(None);
+				history.undo_entries.push(entry);
+				return Err(Error::CannotRestrictUndo);
+			}
+			let redo = entry.undo.apply(data)?;
+			entry.undo.restrict(None);
+			data.increment_generation();
+			data.last_affected = redo.affected();
+			// Keep the original operation's info for the redo entry.
+			history.redo_entries.push(HistoryEntry {
+				info: entry.info.clone(),
+				undo: redo.undo,
+			});
+			if entry.undo.has_pending() {
+				history.undo_entries.push(entry);
+			}
 		}
+		history.commit();
 		Ok(())
 	} else {
 		panic!("undo not supported")
@@ -79,8 +141,15 @@ pub fn redo(palette: &mut Palette) -> Result<()> {
 		// Check for history entry.
 		if let Some(mut entry) = history.redo_entries.pop() {
 			let undo = entry.undo.apply(data)?;
-			history.undo_entries.push(undo);
+			data.increment_generation();
+			data.last_affected = undo.affected();
+			// Keep the original operation's info for the undo entry.
+			history.undo_entries.push(HistoryEntry {
+				info: entry.info,
+				undo: undo.undo,
+			});
 		}
+		history.commit();
 		Ok(())
 	} else {
 		panic!("undo not supported")
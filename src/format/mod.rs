@@ -31,16 +31,19 @@
 #[warn(missing_docs)]
 pub mod zpl;
 #[warn(missing_docs)]
+pub mod ansi;
+#[warn(missing_docs)]
 pub mod default;
 
 // Module imports.
 use Palette;
-use address::Reference;
+use address::{Address, Reference, Selection};
 use data::Data;
 use operation::PaletteOperation;
 use result::Result;
 
 // Standard imports.
+use std::fmt;
 use std::io;
 
 
@@ -48,7 +51,7 @@ use std::io;
 // Format
 ////////////////////////////////////////////////////////////////////////////////
 /// An enum of the supported palette formats.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Format {
 	/// The default palette format; provides no special behaviors or 
 	/// restrictions.
@@ -64,6 +67,7 @@ pub enum Format {
 impl Format {
 	/// Called when a new palette is created. Initializes the palette data.
 	pub fn initialize(self, data: &mut Data)  {
+		data.format = self;
 		match self {
 			Format::Zpl => zpl::initialize(data),
 			_ => (),
@@ -109,6 +113,14 @@ impl Format {
 		default::undo(palette)
 	}
 
+	/// Reverses the changes made by the most recently applied operation which 
+	/// lie within the given selection.
+	pub fn undo_within(self, palette: &mut Palette, selection: &Selection) 
+		-> Result<()>
+	{
+		default::undo_within(palette, selection)
+	}
+
 	/// Reverses the most recently applied undo operation.
 	pub fn redo(self, palette: &mut Palette) -> Result<()> {
 		default::redo(palette)
@@ -116,15 +128,55 @@ impl Format {
 
 	/// Writes the palette to the given buffer.
 	#[allow(unused_variables)]
-	pub fn write_palette<W>(self, palette: &Palette, out_buf: &mut W) -> io::Result<()> 
+	pub fn write_palette<W>(self, palette: &Palette, out_buf: &mut W) -> Result<()> 
 		where W: io::Write
 	{
 		unimplemented!()
 	}
 
+	/// Returns warnings describing the data which would be lost by exporting
+	/// the given `Data` in the format.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::format::CompatibilityWarning;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// for &address in [
+	/// 	Address::new(0, 0, 16), 
+	/// 	Address::new(0, 14, 0)].iter() 
+	/// {
+	/// 	*dat.create_cell(address).unwrap().borrow_mut() 
+	/// 		= Expression::Color(Color::new(8, 8, 8));
+	/// }
+	/// *dat.create_cell(Address::new(0, 0, 0)).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(10, 10, 10));
+	/// dat.create_cell(Address::new(0, 0, 17)).unwrap();
+	/// dat.set_role("accent", Address::new(0, 0, 0));
+	///
+	/// assert_eq!(Format::Default.export_compatibility(&dat), vec![]);
+	/// assert_eq!(Format::Zpl.export_compatibility(&dat), vec![
+	/// 	CompatibilityWarning::OutOfBounds(Address::new(0, 0, 16)),
+	/// 	CompatibilityWarning::OutOfBounds(Address::new(0, 14, 0)),
+	/// 	CompatibilityWarning::ColorsTruncated(1),
+	/// 	CompatibilityWarning::RolesDropped(1),
+	/// ]);
+	/// ```
+	pub fn export_compatibility(self, data: &Data) 
+		-> Vec<CompatibilityWarning>
+	{
+		match self {
+			Format::Zpl => zpl::compatibility(data),
+			Format::Default => Vec::new(),
+		}
+	}
+
 	/// Reads a palette from the given buffer.
 	#[allow(unused_variables)]
-	pub fn read_palette<R>(self, in_buf: &mut R) -> io::Result<()> 
+	pub fn read_palette<R>(self, in_buf: &mut R) -> Result<()> 
 		where R: io::Read
 	{
 		unimplemented!()
@@ -132,3 +184,39 @@ impl Format {
 }
 
 
+
+////////////////////////////////////////////////////////////////////////////////
+// CompatibilityWarning
+////////////////////////////////////////////////////////////////////////////////
+/// Describes palette data which would be lost by exporting to a `Format`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum CompatibilityWarning {
+	/// The cell at the given address lies outside the format's layout, and 
+	/// will be dropped.
+	OutOfBounds(Address),
+	/// The given number of roles cannot be stored by the format, and will be
+	/// dropped.
+	RolesDropped(usize),
+	/// The given number of colors cannot be stored at the format's color 
+	/// depth, and will be truncated.
+	ColorsTruncated(usize),
+}
+
+
+impl fmt::Display for CompatibilityWarning {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			CompatibilityWarning::OutOfBounds(ref address) => write!(f, 
+				"cell at {} lies outside the format's layout and will be \
+				dropped", address),
+
+			CompatibilityWarning::RolesDropped(count) => write!(f, 
+				"{} role(s) cannot be stored by the format and will be dropped", 
+				count),
+
+			CompatibilityWarning::ColorsTruncated(count) => write!(f, 
+				"{} color(s) exceed the format's color depth and will be \
+				truncated", count),
+		}
+	}
+}
@@ -31,7 +31,9 @@
 use address::{
 	Reference,
 	Page, Line, Column};
+use colors;
 use data::Data;
+use format::CompatibilityWarning;
 
 
 const ZPL_COLOR_DEPTH_SCALE: f32 = 0.25;
@@ -77,6 +79,7 @@ const ZPL_FOOTER_E : [u8;36] = [
 const ZPL_PAGE_LIMIT: Page =  0x203;
 const ZPL_DEFAULT_LINE_LIMIT: Line =  16;
 const ZPL_DEFAULT_COLUMN_LIMIT: Column =  16;
+const ZPL_MAIN_LINE_LIMIT: Line = 14;
 
 const MAIN_PAGE_LIMIT: Page = 0;
 const LEVEL_PAGE_LIMIT: Page = 512;
@@ -110,6 +113,52 @@ pub fn initialize(data: &mut Data) {
 }
 
 	
+/// Returns the warnings for data which would be lost by exporting the given 
+/// data in the ZPL format. Empty cells are not exported, so they are ignored.
+#[cfg_attr(feature = "cargo-clippy", allow(absurd_extreme_comparisons))]
+pub fn compatibility(data: &Data) -> Vec<CompatibilityWarning> {
+	let mut warnings: Vec<CompatibilityWarning> = data.cells
+		.iter()
+		.filter(|&(_, cell)| cell.color().is_some())
+		.map(|(address, _)| address)
+		.filter(|address| {
+			let lines = if address.page <= MAIN_PAGE_LIMIT {
+				ZPL_MAIN_LINE_LIMIT
+			} else {
+				ZPL_DEFAULT_LINE_LIMIT
+			};
+			address.page >= ZPL_PAGE_LIMIT ||
+			address.line >= lines ||
+			address.column >= ZPL_DEFAULT_COLUMN_LIMIT
+		})
+		.map(|&address| CompatibilityWarning::OutOfBounds(address))
+		.collect();
+
+	let truncated = data.cells
+		.values()
+		.filter_map(|cell| cell.color())
+		.filter(|&color| colors::octets(color)
+			.iter()
+			.any(|&octet| stored_octet(octet) != octet))
+		.count();
+	if truncated > 0 {
+		warnings.push(CompatibilityWarning::ColorsTruncated(truncated));
+	}
+
+	if !data.roles.is_empty() {
+		warnings.push(CompatibilityWarning::RolesDropped(data.roles.len()));
+	}
+	warnings
+}
+
+
+/// Returns the given color octet as it is stored at the ZPL color depth.
+fn stored_octet(octet: u8) -> u8 {
+	((octet as f32 * ZPL_COLOR_DEPTH_SCALE) as u8 as f32 
+		/ ZPL_COLOR_DEPTH_SCALE) as u8
+}
+
+	
 /// The function to call when a new page is created.
 #[cfg_attr(feature = "cargo-clippy", allow(absurd_extreme_comparisons))]
 pub fn prepare_new_page(data: &mut Data, group: &Reference) {
@@ -117,7 +166,7 @@ pub fn prepare_new_page(data: &mut Data, group: &Reference) {
 	if page <= MAIN_PAGE_LIMIT {
 		data.set_name(group.clone(), "Main");
 		data.set_label(group.clone(), "Level 0");
-		data.set_line_count(group.clone(), 14);
+		data.set_line_count(group.clone(), ZPL_MAIN_LINE_LIMIT);
 	} else if page <= LEVEL_PAGE_LIMIT {
 		data.set_label(group.clone(), format!("Level {}", page));
 	} else {
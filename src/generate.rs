@@ -0,0 +1,196 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides functions for generating collections of colors outside of the 
+//! palette structure.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use colors;
+
+// Non-local imports.
+use color::Color;
+
+
+
+/// The golden angle in degrees.
+const GOLDEN_ANGLE: f32 = 137.507_77;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Rng
+////////////////////////////////////////////////////////////////////////////////
+/// A small xorshift pseudo-random number generator. Used so that generated 
+/// colors are reproducible from a seed.
+#[derive(Debug, Clone)]
+struct Rng {
+	/// The generator state. Never zero.
+	state: u64,
+}
+
+
+impl Rng {
+	/// Creates a new generator from the given seed.
+	fn new(seed: u64) -> Rng {
+		Rng {
+			state: if seed == 0 {0x9E37_79B9_7F4A_7C15} else {seed},
+		}
+	}
+
+	/// Returns the next pseudo-random number.
+	fn next_u64(&mut self) -> u64 {
+		self.state ^= self.state << 13;
+		self.state ^= self.state >> 7;
+		self.state ^= self.state << 17;
+		self.state
+	}
+
+	/// Returns a pseudo-random number between the given bounds.
+	fn next_in(&mut self, (min, max): (f32, f32)) -> f32 {
+		let t = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+		min + (max - min) * t
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RandomConstraints
+////////////////////////////////////////////////////////////////////////////////
+/// Bounds on the HSL components of randomly generated colors. Hues are given in
+/// degrees, and saturations and lightnesses are given as ratios between 0 and 
+/// 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomConstraints {
+	/// The minimum and maximum hue.
+	hue: (f32, f32),
+	/// The minimum and maximum saturation.
+	saturation: (f32, f32),
+	/// The minimum and maximum lightness.
+	lightness: (f32, f32),
+}
+
+
+impl RandomConstraints {
+	/// Creates a new RandomConstraints allowing any color.
+	#[inline]
+	pub fn new() -> RandomConstraints {
+		Default::default()
+	}
+
+	/// Sets the hue bounds.
+	pub fn hue(mut self, min: f32, max: f32) -> RandomConstraints {
+		self.hue = (min, max);
+		self
+	}
+
+	/// Sets the saturation bounds.
+	pub fn saturation(mut self, min: f32, max: f32) -> RandomConstraints {
+		self.saturation = (min, max);
+		self
+	}
+
+	/// Sets the lightness bounds.
+	pub fn lightness(mut self, min: f32, max: f32) -> RandomConstraints {
+		self.lightness = (min, max);
+		self
+	}
+}
+
+
+impl Default for RandomConstraints {
+	fn default() -> Self {
+		RandomConstraints {
+			hue: (0.0, 360.0),
+			saturation: (0.0, 1.0),
+			lightness: (0.0, 1.0),
+		}
+	}
+}
+
+
+
+/// Returns `count` random colors within the given constraints. The same seed 
+/// always produces the same colors.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::generate::{generate_random, RandomConstraints};
+///
+/// let constraints = RandomConstraints::new().lightness(0.25, 0.75);
+/// let a = generate_random(7, 16, constraints);
+/// let b = generate_random(7, 16, constraints);
+/// let c = generate_random(8, 16, constraints);
+///
+/// assert_eq!(a.len(), 16);
+/// assert_eq!(a, b);
+/// assert!(a != c);
+/// ```
+pub fn generate_random(
+	seed: u64, 
+	count: usize, 
+	constraints: RandomConstraints) 
+	-> Vec<Color>
+{
+	let mut rng = Rng::new(seed);
+	(0..count)
+		.map(|_| {
+			let h = rng.next_in(constraints.hue);
+			let s = rng.next_in(constraints.saturation);
+			let l = rng.next_in(constraints.lightness);
+			colors::from_hsl([h, s, l])
+		})
+		.collect()
+}
+
+/// Returns `count` visually distinct colors with the given saturation and 
+/// lightness. Each hue is separated from the previous one by the golden angle,
+/// so that colors remain well separated however many are generated.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::colors::hsl;
+/// use palette::generate::generate_distinct;
+///
+/// let colors = generate_distinct(8, 0.8, 0.5);
+/// assert_eq!(colors.len(), 8);
+///
+/// for pair in colors.windows(2) {
+/// 	let step = (hsl(pair[1])[0] - hsl(pair[0])[0] + 360.0) % 360.0;
+/// 	assert!((step - 137.5).abs() < 2.0);
+/// }
+/// ```
+pub fn generate_distinct(count: usize, saturation: f32, lightness: f32) 
+	-> Vec<Color>
+{
+	(0..count)
+		.map(|i| {
+			let h = (i as f32 * GOLDEN_ANGLE) % 360.0;
+			colors::from_hsl([h, saturation, lightness])
+		})
+		.collect()
+}
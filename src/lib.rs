@@ -49,17 +49,23 @@ pub mod address;
 #[warn(missing_docs)]
 pub mod cell;
 #[warn(missing_docs)]
+pub mod colors;
+#[warn(missing_docs)]
 pub mod data;
 #[warn(missing_docs)]
 pub mod expression;
 #[warn(missing_docs)]
 pub mod format;
 #[warn(missing_docs)]
+pub mod generate;
+#[warn(missing_docs)]
 pub mod operation;
 #[warn(missing_docs)]
 pub mod result;
 #[warn(missing_docs)]
 pub mod utilities;
+#[warn(missing_docs)]
+pub mod view;
 
 
 
@@ -76,12 +82,16 @@ pub use format::Format;
 
 
 // Local imports.
+use address::Selection;
 use data::Data;
-use operation::{PaletteOperation, OperationHistory};
+use operation::{PaletteOperation, OperationHistory, OperationInfo};
 use result::Result;
 
 // Standard imports.
 use std::fmt;
+use std::io;
+use std::mem;
+use std::time::Duration;
 
 
 
@@ -96,9 +106,6 @@ pub struct Palette {
 
 	/// The operation undo and redo history.
 	operation_history: Option<OperationHistory>,
-	
-	/// The palette format.
-	format: Format,
 }
 
 
@@ -114,7 +121,6 @@ impl Palette {
 				} else {
 				    None
 				},
-			format: format,
 		};
 		
 		pal.data.set_name(Reference::all(), name.into());
@@ -132,6 +138,11 @@ impl Palette {
 		self.data.is_empty()
 	}
 
+	/// Returns the `Palette`'s format.
+	pub fn format(&self) -> Format {
+		self.data.format
+	}
+
 	/// Returns the total number of history entries recorded.
 	pub fn history_len(&self) -> (usize, usize) {
 		if let Some(ref history) = self.operation_history {
@@ -150,11 +161,379 @@ impl Palette {
 		}
 	}
 
+	/// Returns information about the operations which can be undone, from least
+	/// to most recent.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertCell::new())).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(DeleteCell::new(Address::new(0, 0, 0)))).unwrap();
+	///
+	/// let names: Vec<_> = pal.history().map(|info| info.name).collect();
+	/// assert_eq!(names, vec!["Insert Cell", "Insert Color", "Remove Cell"]);
+	///
+	/// pal.undo().unwrap();
+	/// assert_eq!(pal.history().count(), 2);
+	/// let names: Vec<_> = pal.redo_history().map(|info| info.name).collect();
+	/// assert_eq!(names, vec!["Remove Cell"]);
+	/// ```
+	pub fn history<'a>(&'a self) -> impl Iterator<Item=&'a OperationInfo> {
+		self.operation_history
+			.iter()
+			.flat_map(|history| history.undo_entries.iter())
+			.map(|entry| &entry.info)
+	}
+
+	/// Returns information about the operations which can be redone, from least
+	/// to most recently undone.
+	pub fn redo_history<'a>(&'a self) 
+		-> impl Iterator<Item=&'a OperationInfo> 
+	{
+		self.operation_history
+			.iter()
+			.flat_map(|history| history.redo_entries.iter())
+			.map(|entry| &entry.info)
+	}
+
+	/// Returns whether the `Palette` contains the same collection of colors as
+	/// the given `Palette`, regardless of their addresses.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut a = Palette::new("A", Format::Default, false);
+	/// let mut b = Palette::new("B", Format::Default, false);
+	/// let mut c = Palette::new("C", Format::Default, false);
+	///
+	/// a.apply(Box::new(InsertColor::new(Color::new(10, 20, 30)))).unwrap();
+	/// a.apply(Box::new(InsertColor::new(Color::new(40, 50, 60)))).unwrap();
+	/// b.apply(Box::new(InsertColor::new(Color::new(40, 50, 60))
+	/// 	.located_at(Address::new(2, 0, 3)))).unwrap();
+	/// b.apply(Box::new(InsertColor::new(Color::new(10, 20, 30))
+	/// 	.located_at(Address::new(5, 1, 0)))).unwrap();
+	/// c.apply(Box::new(InsertColor::new(Color::new(10, 20, 30)))).unwrap();
+	/// c.apply(Box::new(InsertColor::new(Color::new(40, 50, 61)))).unwrap();
+	///
+	/// assert!(a.color_set_eq(&b));
+	/// assert!(!a.color_set_eq(&c));
+	/// ```
+	pub fn color_set_eq(&self, other: &Palette) -> bool {
+		self.data.color_set_eq(&other.data)
+	}
+
+	/// Sets the time window within which consecutive operations with the same
+	/// name that modify the same addresses are merged into a single history 
+	/// entry. If the window is None, operations are never merged.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use std::time::Duration;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.set_history_coalescing(Some(Duration::from_secs(60)));
+	///
+	/// let address = Address::new(0, 0, 0);
+	/// for &v in [10, 20, 30].iter() {
+	/// 	pal.apply(Box::new(InsertColor::new(Color::new(v, v, v))
+	/// 		.located_at(address)
+	/// 		.overwrite(true))).unwrap();
+	/// }
+	/// assert_eq!(pal.history_len(), (1, 0));
+	///
+	/// pal.undo().unwrap();
+	/// assert_eq!(pal.color(address), None);
+	/// ```
+	pub fn set_history_coalescing(&mut self, window: Option<Duration>) {
+		if let Some(ref mut history) = self.operation_history {
+			history.coalesce_window = window;
+		}
+	}
+
+	/// Prevents the next applied operation from being merged into the most 
+	/// recent history entry.
+	pub fn commit_history(&mut self) {
+		if let Some(ref mut history) = self.operation_history {
+			history.commit();
+		}
+	}
+
+	/// Returns the name of the given group, or None if it has no name.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, false);
+	/// let page = Reference::page_of(&Address::new(3, 0, 0));
+	/// pal.set_name(page.clone(), "Terrain");
+	///
+	/// assert_eq!(pal.name(&Reference::all()), Some("Example"));
+	/// assert_eq!(pal.name(&page), Some("Terrain"));
+	/// ```
+	pub fn name(&self, group: &Reference) -> Option<&str> {
+		self.data.name(group)
+	}
+
+	/// Sets the name of the given group.
+	pub fn set_name<S>(&mut self, group: Reference, name: S) 
+		where S: Into<String>
+	{
+		self.data.set_name(group, name);
+	}
+
+	/// Returns the color of the cell assigned to the given role, or None if 
+	/// the role is not assigned or the cell is empty. The color follows changes
+	/// to the cell.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, false);
+	/// let address = Address::new(0, 0, 0);
+	/// pal.set_role("accent", address);
+	/// assert_eq!(pal.role_color("accent"), None);
+	///
+	/// pal.apply(Box::new(InsertColor::new(Color::new(200, 40, 40)))).unwrap();
+	/// assert_eq!(pal.role_color("accent"), Some(Color::new(200, 40, 40)));
+	///
+	/// pal.apply(Box::new(SetColor::new(address, Color::new(40, 200, 40))))
+	/// 	.unwrap();
+	/// assert_eq!(pal.role_color("accent"), Some(Color::new(40, 200, 40)));
+	/// assert_eq!(pal.role_color("background"), None);
+	/// ```
+	pub fn role_color(&self, role: &str) -> Option<Color> {
+		self.data.role_color(role)
+	}
+
+	/// Assigns the given role to the given address.
+	pub fn set_role<S>(&mut self, role: S, address: Address) 
+		where S: Into<String>
+	{
+		self.data.set_role(role, address);
+	}
+
+	/// Returns the number of operations, undos, and redos which have modified 
+	/// the `Palette`. Any cache of resolved colors made at an earlier 
+	/// generation may be stale.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// let generation = pal.generation();
+	///
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// assert!(pal.generation() > generation);
+	///
+	/// let generation = pal.generation();
+	/// pal.color(Address::new(0, 0, 0));
+	/// assert_eq!(pal.generation(), generation);
+	/// ```
+	pub fn generation(&self) -> u64 {
+		self.data.generation()
+	}
+
+	/// Writes a preview of the `Palette` to the given buffer using ANSI escape
+	/// sequences. If `truecolor` is false, the nearest xterm-256 colors are 
+	/// used instead of 24-bit colors.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, false);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(255, 0, 0)))).unwrap();
+	///
+	/// let mut out = Vec::new();
+	/// pal.write_ansi(&mut out, true).unwrap();
+	/// assert!(String::from_utf8(out).unwrap().contains("\x1b[48;2;255;0;0m"));
+	///
+	/// let mut out = Vec::new();
+	/// pal.write_ansi(&mut out, false).unwrap();
+	/// assert!(String::from_utf8(out).unwrap().contains("\x1b[48;5;196m"));
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns an `Io` error if the buffer cannot be written.
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::result::Error;
+	/// use std::io;
+	///
+	/// struct FailingWriter;
+	///
+	/// impl io::Write for FailingWriter {
+	/// 	fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+	/// 		Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+	/// 	}
+	/// 	fn flush(&mut self) -> io::Result<()> { Ok(()) }
+	/// }
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, false);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(255, 0, 0)))).unwrap();
+	///
+	/// match pal.write_ansi(&mut FailingWriter, true) {
+	/// 	Err(Error::Io(_)) => (),
+	/// 	_ => panic!("expected Io error"),
+	/// }
+	/// ```
+	pub fn write_ansi<W>(&self, out_buf: &mut W, truecolor: bool) 
+		-> Result<()>
+		where W: io::Write
+	{
+		format::ansi::write_ansi(&self.data, out_buf, truecolor)
+	}
+
+	/// Returns the most recently inserted colors, from oldest to newest.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, false);
+	/// pal.set_recent_capacity(3);
+	/// for &v in [10, 20, 20, 30, 40].iter() {
+	/// 	pal.apply(Box::new(InsertColor::new(Color::new(v, v, v)))).unwrap();
+	/// }
+	///
+	/// assert_eq!(pal.recent_colors(), &[
+	/// 	Color::new(20, 20, 20), 
+	/// 	Color::new(30, 30, 30), 
+	/// 	Color::new(40, 40, 40),
+	/// ]);
+	/// ```
+	pub fn recent_colors(&self) -> &[Color] {
+		self.data.recent_colors()
+	}
+
+	/// Sets the maximum number of recent colors to keep.
+	pub fn set_recent_capacity(&mut self, capacity: usize) {
+		self.data.set_recent_capacity(capacity);
+	}
+
+	/// Returns whether the given address is locked against modification.
+	pub fn is_locked(&self, address: Address) -> bool {
+		self.data.is_locked(address)
+	}
+
+	/// Locks or unlocks the given address against modification by operations.
+	pub fn set_locked(&mut self, address: Address, locked: bool) {
+		self.data.set_locked(address, locked);
+	}
+
+	/// Sets the maximum number of distinct colors allowed in the `Palette`, or 
+	/// None to remove the limit. Colors equal to an existing color do not count
+	/// against the budget.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::result::Error;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, false);
+	/// pal.set_color_budget(Some(256));
+	/// for i in 0..256 {
+	/// 	pal.apply(Box::new(InsertColor::new(Color::new(i as u8, 0, 0))))
+	/// 		.unwrap();
+	/// }
+	///
+	/// match pal.apply(Box::new(InsertColor::new(Color::new(0, 1, 0)))) {
+	/// 	Err(Error::BudgetExceeded(256)) => (),
+	/// 	_ => panic!("expected BudgetExceeded error"),
+	/// }
+	/// assert_eq!(pal.len(), 256);
+	/// assert!(pal.apply(Box::new(InsertColor::new(Color::new(5, 0, 0)))).is_ok());
+	/// assert_eq!(pal.len(), 257);
+	/// ```
+	pub fn set_color_budget(&mut self, budget: Option<usize>) {
+		self.data.set_color_budget(budget);
+	}
+
 	/// Returns the color at the given address, or None if the cell is empty.
 	pub fn color(&self, address: Address) -> Option<Color> {
 		self.data.cell(address).and_then(|cell| cell.color())
 	}
 
+	/// Returns the addresses modified by the most recent operation, undo, or 
+	/// redo, in address order.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(100, 100, 100)))).unwrap();
+	///
+	/// pal.apply(Box::new(InsertRamp::new(
+	/// 	Address::new(0, 0, 0), 
+	/// 	Address::new(0, 0, 1), 
+	/// 	3))).unwrap();
+	/// assert_eq!(pal.last_affected(), &[
+	/// 	Address::new(0, 0, 2),
+	/// 	Address::new(0, 0, 3),
+	/// 	Address::new(0, 0, 4),
+	/// ]);
+	///
+	/// pal.undo().unwrap();
+	/// assert_eq!(pal.last_affected().len(), 3);
+	/// ```
+	pub fn last_affected(&self) -> &[Address] {
+		&self.data.last_affected
+	}
+
+	/// Returns the wall-clock time taken to apply the most recent operation, or
+	/// None if no operation has been applied.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # #[cfg(feature = "timing")] {
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, false);
+	/// assert!(pal.last_operation_time().is_none());
+	///
+	/// pal.apply(Box::new(InsertCell::new())).unwrap();
+	/// assert!(pal.last_operation_time().is_some());
+	/// # }
+	/// ```
+	#[cfg(feature = "timing")]
+	pub fn last_operation_time(&self) -> Option<Duration> {
+		self.data.last_operation_time()
+	}
+
 
 	/// Applies the given operation to the `Palette`. Usually, this will just 
 	/// defer to the `PaletteOperation`'s apply method, but this could also 
@@ -166,19 +545,138 @@ impl Palette {
 		operation: Box<PaletteOperation>)
 		-> Result<()> 
 	{
-		self.format.apply_operation(self, operation)
+		self.data.format.apply_operation(self, operation)
+	}
+
+	/// Applies each of the given operations to the `Palette` in order, 
+	/// returning the result of each. If `rollback` is true, the first failure
+	/// stops the batch and reverts every operation applied by it, including 
+	/// their history entries, and the remaining operations are not applied.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// let ops: Vec<Box<PaletteOperation>> = vec![
+	/// 	Box::new(InsertColor::new(Color::new(10, 10, 10))),
+	/// 	Box::new(InsertColor::new(Color::new(20, 20, 20))),
+	/// 	Box::new(InsertColor::new(Color::new(30, 30, 30))),
+	/// ];
+	///
+	/// let results = pal.apply_all(ops, true);
+	/// assert!(results.iter().all(|r| r.is_ok()));
+	/// assert_eq!(pal.len(), 3);
+	///
+	/// let ops: Vec<Box<PaletteOperation>> = vec![
+	/// 	Box::new(InsertColor::new(Color::new(40, 40, 40))),
+	/// 	Box::new(DeleteCell::new(Address::new(9, 9, 9))),
+	/// 	Box::new(InsertColor::new(Color::new(50, 50, 50))),
+	/// ];
+	///
+	/// let results = pal.apply_all(ops, true);
+	/// assert_eq!(results.len(), 2);
+	/// assert!(results[1].is_err());
+	/// assert_eq!(pal.len(), 3);
+	/// assert_eq!(pal.history_len(), (3, 0));
+	/// ```
+	pub fn apply_all<I>(&mut self, operations: I, rollback: bool) 
+		-> Vec<Result<()>>
+		where I: IntoIterator<Item=Box<PaletteOperation>>
+	{
+		// Save the state needed to revert the batch.
+		let snapshot = if rollback {Some(self.data.clone())} else {None};
+		let mut saved_history = None;
+		if let Some(ref mut history) = self.operation_history {
+			if rollback {
+				history.commit();
+				saved_history = Some((
+					history.undo_entries.len(),
+					mem::replace(&mut history.redo_entries, Vec::new())
+				));
+			}
+		}
+
+		let mut results = Vec::new();
+		for operation in operations {
+			let result = self.apply(operation);
+			let failed = result.is_err();
+			results.push(result);
+
+			if failed && rollback {
+				if let Some(snapshot) = snapshot {
+					self.data.restore(snapshot);
+				}
+				if let Some(ref mut history) = self.operation_history {
+					if let Some((undo_len, redo_entries)) = saved_history {
+						history.undo_entries.truncate(undo_len);
+						history.redo_entries = redo_entries;
+					}
+					history.commit();
+				}
+				return results;
+			}
+		}
+		
+		// Restore the redo entries if nothing was applied.
+		if let Some(ref mut history) = self.operation_history {
+			if let Some((undo_len, redo_entries)) = saved_history {
+				if history.undo_entries.len() == undo_len {
+					history.redo_entries = redo_entries;
+				}
+			}
+		}
+		results
 	}
 
 	/// Reverses the most recently applied operation.
 	#[allow(unused_variables)]
 	pub fn undo(&mut self) -> Result<()> {
-		self.format.undo(self)
+		self.data.format.undo(self)
+	}
+
+	/// Reverses the changes made by the most recently applied operation which 
+	/// lie within the given selection. The remaining changes stay on the undo 
+	/// stack, so a later undo reverts them.
+	///
+	/// # Errors
+	///
+	/// Returns a `CannotRestrictUndo` error if the operation's changes cannot
+	/// be separated, such as after a `ConvertFormat` operation.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::address::Selection;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// let (a, b) = (Address::new(0, 0, 0), Address::new(1, 0, 0));
+	/// pal.apply(Box::new(Sequence::new(vec![
+	/// 	Box::new(InsertColor::new(Color::new(10, 10, 10)).located_at(a)),
+	/// 	Box::new(InsertColor::new(Color::new(20, 20, 20)).located_at(b)),
+	/// ]))).unwrap();
+	///
+	/// pal.undo_within(Selection::page(0)).unwrap();
+	/// assert_eq!(pal.color(a), None);
+	/// assert_eq!(pal.color(b), Some(Color::new(20, 20, 20)));
+	/// assert_eq!(pal.history_len(), (1, 1));
+	///
+	/// pal.undo().unwrap();
+	/// assert_eq!(pal.color(b), None);
+	/// assert_eq!(pal.history_len(), (0, 2));
+	/// ```
+	pub fn undo_within(&mut self, selection: Selection) -> Result<()> {
+		self.data.format.undo_within(self, &selection)
 	}
 
 	/// Reverses the most recently applied undo operation.
 	#[allow(unused_variables)]
 	pub fn redo(&mut self) -> Result<()> {
-		self.format.redo(self)
+		self.data.format.redo(self)
 	}
 }
 
@@ -190,7 +688,6 @@ impl Default for Palette {
 		Palette {
 			data: Default::default(),
 			operation_history: None,
-			format: Format::Default,
 		}
 	}
 }
@@ -201,7 +698,7 @@ impl Default for Palette {
 impl fmt::Display for Palette {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "Format: {:?}, History: {:?}\n{}",
-			self.format,
+			self.data.format,
 			self.history_len(),
 			self.data)
 	}
@@ -0,0 +1,766 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines operations for copying and rearranging cells.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use address::{Address, Page, Reference, Selection};
+use colors;
+use data::Data;
+use expression::Expression;
+use operation::{
+	remove_target,
+	revert_on_error,
+	selected_colors,
+	set_target,
+	HistoryEntry,
+	OperationInfo,
+	PaletteOperation,
+	Undo,
+};
+use result::{Error, Result};
+
+// Non-local imports.
+use color::Color;
+
+// Standard imports.
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// CopyCells
+////////////////////////////////////////////////////////////////////////////////
+/// Copies every occupied cell in a `Selection` to the region starting at the 
+/// given address, preserving their relative layout.
+/// 
+/// # Example
+///
+/// ```rust
+/// # extern crate interval;
+/// # extern crate palette;
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::address::Selection;
+/// use interval::Interval;
+/// 
+/// # fn main() {
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(10, 10, 10)))).unwrap();
+/// pal.apply(Box::new(InsertColor::new(Color::new(20, 20, 20))
+/// 	.located_at(Address::new(0, 0, 2)))).unwrap();
+///
+/// let selection = Selection::new(vec![
+/// 	Interval::closed(Address::new(0, 0, 0), Address::new(0, 0, 2))
+/// ]);
+/// pal.apply(Box::new(CopyCells::new(selection, Address::new(1, 3, 4))))
+/// 	.unwrap();
+///
+/// assert_eq!(pal.color(Address::new(1, 3, 4)), Some(Color::new(10, 10, 10)));
+/// assert_eq!(pal.color(Address::new(1, 3, 5)), None);
+/// assert_eq!(pal.color(Address::new(1, 3, 6)), Some(Color::new(20, 20, 20)));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CopyCells {
+	/// The cells to copy.
+	selection: Selection,
+	/// The location of the first copied cell.
+	location: Address,
+	/// Whether to overwrite existing cells in the destination.
+	overwrite: bool,
+}
+
+
+impl CopyCells {
+	/// Creates a new CopyCells operation.
+	#[inline]
+	pub fn new(selection: Selection, location: Address) -> CopyCells {
+		CopyCells {
+			selection: selection,
+			location: location,
+			overwrite: false,
+		}
+	}
+
+	/// Configures the operation to overwrite existing cells in the 
+	/// destination.
+	pub fn overwrite(mut self, overwrite: bool) -> CopyCells {
+		self.overwrite = overwrite;
+		self
+	}
+}
+
+
+impl PaletteOperation for CopyCells {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Copy Cells")
+			.detail("location", self.location)
+			.detail("overwrite", self.overwrite)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		// Collect the selected expressions.
+		let sources: Vec<(Address, Expression)> = data.cells
+			.iter()
+			.filter(|&(address, _)| self.selection.contains(address))
+			.map(|(&address, cell)| (address, *cell.borrow()))
+			.collect();
+
+		// Find the corner of the selected region.
+		let origin = Address::new(
+			sources.iter().map(|&(a, _)| a.page).min().unwrap_or(0),
+			sources.iter().map(|&(a, _)| a.line).min().unwrap_or(0),
+			sources.iter().map(|&(a, _)| a.column).min().unwrap_or(0),
+		);
+
+		// Find and check every target before writing anything.
+		let mut targets = Vec::with_capacity(sources.len());
+		for (address, expr) in sources {
+			let target = offset_address(self.location, address, origin)?;
+			if !self.overwrite && data.cell(target).is_some() {
+				return Err(Error::AddressInUse(target));
+			}
+			if data.is_locked(target) {
+				return Err(Error::SlotLocked(target));
+			}
+			targets.push((target, expr));
+		}
+
+		// Copy each expression relative to the new location.
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (target, expr) in targets {
+				set_target(data, target, expr, undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+/// Returns the address lying at the same position relative to `location` as
+/// `address` does to `origin`. If that address is out of range, the error 
+/// reports the target address, clamped to the largest representable address.
+fn offset_address(location: Address, address: Address, origin: Address) 
+	-> Result<Address>
+{
+	let page = location.page.checked_add(address.page - origin.page);
+	let line = location.line.checked_add(address.line - origin.line);
+	let column = location.column.checked_add(address.column - origin.column);
+
+	match (page, line, column) {
+		(Some(page), Some(line), Some(column)) 
+			=> Ok(Address::new(page, line, column)),
+		_	=> Err(Error::InvalidAddress(Address::new(
+			location.page.saturating_add(address.page - origin.page),
+			location.line.saturating_add(address.line - origin.line),
+			location.column.saturating_add(address.column - origin.column),
+		))),
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DuplicatePage
+////////////////////////////////////////////////////////////////////////////////
+/// Copies every occupied cell on one page to the same line and column on 
+/// another page. The destination page must be empty unless the operation is 
+/// configured to overwrite it, in which case it is made an exact copy of the 
+/// source page.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+/// pal.apply(Box::new(InsertColor::new(Color::new(100, 100, 100)))).unwrap();
+/// pal.apply(Box::new(InsertRamp::new(
+/// 	Address::new(0, 0, 0), 
+/// 	Address::new(0, 0, 1), 
+/// 	3))).unwrap();
+///
+/// pal.apply(Box::new(DuplicatePage::new(0, 2))).unwrap();
+/// assert_eq!(pal.len(), 10);
+/// for column in 0..5 {
+/// 	assert_eq!(
+/// 		pal.color(Address::new(2, 0, column)),
+/// 		pal.color(Address::new(0, 0, column)));
+/// }
+///
+/// // The copy is independent of the original.
+/// pal.apply(Box::new(SetColor::new(
+/// 	Address::new(0, 0, 2), 
+/// 	Color::new(255, 0, 0)))).unwrap();
+/// assert_eq!(pal.color(Address::new(2, 0, 2)), Some(Color::new(25, 25, 25)));
+///
+/// // The destination must be empty.
+/// assert!(pal.apply(Box::new(DuplicatePage::new(0, 2))).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DuplicatePage {
+	/// The page to copy.
+	source: Page,
+	/// The page to copy to.
+	dest: Page,
+	/// Whether to overwrite existing cells on the destination page.
+	overwrite: bool,
+}
+
+
+impl DuplicatePage {
+	/// Creates a new DuplicatePage operation.
+	#[inline]
+	pub fn new(source: Page, dest: Page) -> DuplicatePage {
+		DuplicatePage {
+			source: source,
+			dest: dest,
+			overwrite: false,
+		}
+	}
+
+	/// Configures the operation to replace the contents of the destination 
+	/// page.
+	pub fn overwrite(mut self, overwrite: bool) -> DuplicatePage {
+		self.overwrite = overwrite;
+		self
+	}
+}
+
+
+impl PaletteOperation for DuplicatePage {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Duplicate Page")
+			.detail("source", self.source)
+			.detail("dest", self.dest)
+			.detail("overwrite", self.overwrite)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let source_page = Reference::page_of(&Address::new(self.source, 0, 0));
+		let dest_page = Reference::page_of(&Address::new(self.dest, 0, 0));
+
+		let existing: Vec<Address> = data.cells
+			.keys()
+			.filter(|address| dest_page.contains(address))
+			.cloned()
+			.collect();
+		if !self.overwrite {
+			if let Some(&address) = existing.first() {
+				return Err(Error::AddressInUse(address));
+			}
+		}
+
+		// Collect the source expressions.
+		let sources: Vec<(Address, Expression)> = data.cells
+			.iter()
+			.filter(|&(address, _)| source_page.contains(address))
+			.map(|(&address, cell)| (address, *cell.borrow()))
+			.collect();
+
+		// Copy the expressions, then remove any cells the copy didn't replace.
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			let mut targets = BTreeSet::new();
+			for (address, expr) in sources {
+				let target = Address::new(self.dest, address.line, address.column);
+				set_target(data, target, expr, undo)?;
+				targets.insert(target);
+			}
+			for address in existing {
+				if !targets.contains(&address) {
+					remove_target(data, address, undo)?;
+				}
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// InterleaveRamps
+////////////////////////////////////////////////////////////////////////////////
+/// Writes the colors of two selections alternately into consecutive addresses
+/// starting at the given address.
+/// 
+/// # Example
+///
+/// ```rust
+/// # extern crate interval;
+/// # extern crate palette;
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::address::Selection;
+/// use interval::Interval;
+/// 
+/// # fn main() {
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// for &v in [100, 200].iter() {
+/// 	pal.apply(Box::new(InsertColor::new(Color::new(v, 0, 0)))).unwrap();
+/// }
+/// for &v in [100, 200].iter() {
+/// 	pal.apply(Box::new(InsertColor::new(Color::new(0, 0, v))
+/// 		.located_at(Address::new(0, 1, 0)))).unwrap();
+/// }
+///
+/// let reds = Selection::new(vec![
+/// 	Interval::closed(Address::new(0, 0, 0), Address::new(0, 0, 1))
+/// ]);
+/// let blues = Selection::new(vec![
+/// 	Interval::closed(Address::new(0, 1, 0), Address::new(0, 1, 1))
+/// ]);
+/// pal.apply(Box::new(InterleaveRamps::new(reds, blues, Address::new(1, 0, 0))))
+/// 	.unwrap();
+///
+/// assert_eq!(pal.color(Address::new(1, 0, 0)), Some(Color::new(100, 0, 0)));
+/// assert_eq!(pal.color(Address::new(1, 0, 1)), Some(Color::new(0, 0, 100)));
+/// assert_eq!(pal.color(Address::new(1, 0, 2)), Some(Color::new(200, 0, 0)));
+/// assert_eq!(pal.color(Address::new(1, 0, 3)), Some(Color::new(0, 0, 200)));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InterleaveRamps {
+	/// The cells providing the first, third, fifth, etc. colors.
+	first: Selection,
+	/// The cells providing the second, fourth, sixth, etc. colors.
+	second: Selection,
+	/// The location of the first interleaved color.
+	location: Address,
+	/// Whether to drop the excess colors of the larger selection.
+	truncate: bool,
+	/// Whether to overwrite existing cells in the destination.
+	overwrite: bool,
+}
+
+
+impl InterleaveRamps {
+	/// Creates a new InterleaveRamps operation.
+	#[inline]
+	pub fn new(first: Selection, second: Selection, location: Address) 
+		-> InterleaveRamps 
+	{
+		InterleaveRamps {
+			first: first,
+			second: second,
+			location: location,
+			truncate: false,
+			overwrite: false,
+		}
+	}
+
+	/// Configures the operation to drop the excess colors of the larger 
+	/// selection rather than failing when the selections differ in size.
+	pub fn truncate(mut self, truncate: bool) -> InterleaveRamps {
+		self.truncate = truncate;
+		self
+	}
+
+	/// Configures the operation to overwrite existing cells in the 
+	/// destination.
+	pub fn overwrite(mut self, overwrite: bool) -> InterleaveRamps {
+		self.overwrite = overwrite;
+		self
+	}
+}
+
+
+impl PaletteOperation for InterleaveRamps {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Interleave Ramps")
+			.detail("location", self.location)
+			.detail("truncate", self.truncate)
+			.detail("overwrite", self.overwrite)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let first = selected_colors(data, &self.first);
+		let second = selected_colors(data, &self.second);
+		if first.len() != second.len() && !self.truncate {
+			return Err(Error::SelectionSizeMismatch(first.len(), second.len()));
+		}
+
+		let colors: Vec<_> = first
+			.into_iter()
+			.zip(second.into_iter())
+			.flat_map(|((_, a), (_, b))| vec![a, b])
+			.collect();
+
+		let targets = data.find_targets(
+			colors.len(),
+			self.location,
+			true,
+			None
+		)?;
+
+		// Check every target before writing anything.
+		if !self.overwrite {
+			if let Some(&target) = targets
+				.iter()
+				.find(|&&target| data.cell(target).is_some())
+			{
+				return Err(Error::AddressInUse(target));
+			}
+		}
+
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (target, color) in targets.into_iter().zip(colors.into_iter()) {
+				set_target(data, target, Expression::Color(color), undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SortKey
+////////////////////////////////////////////////////////////////////////////////
+/// The property by which colors are sorted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SortKey {
+	/// Sort by HSL hue.
+	Hue,
+	/// Sort by HSL saturation.
+	Saturation,
+	/// Sort by HSL lightness.
+	Lightness,
+	/// Sort by relative luminance.
+	Luminance,
+}
+
+
+impl SortKey {
+	/// Returns the value of the key for the given color.
+	fn value(self, color: Color) -> f32 {
+		match self {
+			SortKey::Hue => colors::hsl(color)[0],
+			SortKey::Saturation => colors::hsl(color)[1],
+			SortKey::Lightness => colors::hsl(color)[2],
+			SortKey::Luminance => colors::relative_luminance(color),
+		}
+	}
+}
+
+
+impl Default for SortKey {
+	fn default() -> Self {
+		SortKey::Hue
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ScanOrder
+////////////////////////////////////////////////////////////////////////////////
+/// The order in which addresses are visited when placing colors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScanOrder {
+	/// Visit each line of a page in turn, filling its columns.
+	RowMajor,
+	/// Visit each column of a page in turn, filling its lines.
+	ColumnMajor,
+}
+
+
+impl ScanOrder {
+	/// Returns the first `n` addresses of the given palette in scan order.
+	fn addresses(self, data: &mut Data, n: usize) -> Result<Vec<Address>> {
+		if self == ScanOrder::RowMajor {
+			return data.find_targets(n, Address::default(), true, None);
+		}
+
+		let mut targets = Vec::with_capacity(n);
+		for page in 0..data.maximum_page_count {
+			if targets.len() >= n { break; }
+			let lines = data.line_count(
+				&Reference::page_of(&Address::new(page, 0, 0)));
+			let column_counts: Vec<_> = (0..lines)
+				.map(|line| data.column_count(
+					&Reference::line_of(&Address::new(page, line, 0))))
+				.collect();
+			let columns = column_counts.iter().cloned().max().unwrap_or(0);
+
+			for column in 0..columns {
+				for (line, &count) in column_counts.iter().enumerate() {
+					if targets.len() < n && column < count {
+						targets.push(Address::new(page, line as u8, column));
+					}
+				}
+			}
+		}
+
+		if targets.len() < n {
+			Err(Error::MaxCellLimitExceeded)
+		} else {
+			Ok(targets)
+		}
+	}
+}
+
+
+impl Default for ScanOrder {
+	fn default() -> Self {
+		ScanOrder::RowMajor
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SortPalette
+////////////////////////////////////////////////////////////////////////////////
+/// Sorts every color in the palette by the given key, placing them from the 
+/// first address in the given scan order. Cells which are vacated by the sort
+/// are removed.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::data::Data;
+/// use palette::operation::*;
+/// 
+/// let mut dat: Data = Default::default();
+/// dat.maximum_page_count = 2;
+/// dat.default_line_count = 1;
+/// dat.default_column_count = 2;
+/// let colors = [
+/// 	Color::new(0, 0, 255), 
+/// 	Color::new(0, 255, 0), 
+/// 	Color::new(255, 255, 0), 
+/// 	Color::new(255, 0, 0),
+/// ];
+/// for (i, &color) in colors.iter().enumerate() {
+/// 	let address = Address::new(i as u16 / 2, 0, i as u8 % 2);
+/// 	*dat.create_cell(address).unwrap().borrow_mut() = Expression::Color(color);
+/// }
+///
+/// let mut op = SortPalette::new(SortKey::Hue, ScanOrder::RowMajor);
+/// op.apply(&mut dat).unwrap();
+///
+/// let color = |address| dat.cell(address).and_then(|cell| cell.color());
+/// assert_eq!(color(Address::new(0, 0, 0)), Some(Color::new(255, 0, 0)));
+/// assert_eq!(color(Address::new(0, 0, 1)), Some(Color::new(255, 255, 0)));
+/// assert_eq!(color(Address::new(1, 0, 0)), Some(Color::new(0, 255, 0)));
+/// assert_eq!(color(Address::new(1, 0, 1)), Some(Color::new(0, 0, 255)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SortPalette {
+	/// The property to sort by.
+	key: SortKey,
+	/// The order in which to place the sorted colors.
+	order: ScanOrder,
+}
+
+
+impl SortPalette {
+	/// Creates a new SortPalette operation.
+	#[inline]
+	pub fn new(key: SortKey, order: ScanOrder) -> SortPalette {
+		SortPalette {
+			key: key,
+			order: order,
+		}
+	}
+}
+
+
+impl PaletteOperation for SortPalette {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Sort Palette")
+			.detail("key", format!("{:?}", self.key))
+			.detail("order", format!("{:?}", self.order))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut sources: Vec<(Address, Color)> = data.cells
+			.iter()
+			.filter_map(|(&address, cell)| cell.color().map(|c| (address, c)))
+			.collect();
+		let key = self.key;
+		sources.sort_by(|&(_, a), &(_, b)| key.value(a)
+			.partial_cmp(&key.value(b))
+			.unwrap_or(Ordering::Equal));
+
+		let targets = self.order.addresses(data, sources.len())?;
+
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (&target, &(_, color)) in targets.iter().zip(sources.iter()) {
+				set_target(data, target, Expression::Color(color), undo)?;
+			}
+
+			// Remove the vacated cells.
+			for &(address, _) in &sources {
+				if !targets.contains(&address) {
+					remove_target(data, address, undo)?;
+				}
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Remap
+////////////////////////////////////////////////////////////////////////////////
+/// Moves every cell in the palette to the address given by a mapping function.
+///
+/// # Errors
+///
+/// Returns an `AddressInUse` error if two cells are mapped to the same 
+/// address, an `InvalidAddress` error if a cell is mapped outside of the 
+/// palette, and a `SlotLocked` error if a moved cell or its new address is 
+/// locked. The palette is not modified if any of these checks fail.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(10, 10, 10)))).unwrap();
+/// pal.apply(Box::new(InsertColor::new(Color::new(20, 20, 20)))).unwrap();
+///
+/// pal.apply(Box::new(Remap::new(Box::new(|address: Address| 
+/// 	Address::new(2, address.line, address.column))))).unwrap();
+///
+/// assert_eq!(pal.len(), 2);
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), None);
+/// assert_eq!(pal.color(Address::new(2, 0, 0)), Some(Color::new(10, 10, 10)));
+/// assert_eq!(pal.color(Address::new(2, 0, 1)), Some(Color::new(20, 20, 20)));
+///
+/// assert!(pal.apply(Box::new(Remap::new(Box::new(|_| 
+/// 	Address::new(0, 0, 0))))).is_err());
+/// ```
+pub struct Remap {
+	/// The function giving the new address of each cell.
+	mapping: Box<Fn(Address) -> Address>,
+}
+
+
+impl Remap {
+	/// Creates a new Remap operation using the given mapping function.
+	#[inline]
+	pub fn new(mapping: Box<Fn(Address) -> Address>) -> Remap {
+		Remap {
+			mapping: mapping,
+		}
+	}
+}
+
+
+impl fmt::Debug for Remap {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "Remap {{ mapping: <function> }}")
+	}
+}
+
+
+impl PaletteOperation for Remap {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Remap")
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let sources: Vec<(Address, Expression)> = data.cells
+			.iter()
+			.map(|(&address, cell)| (address, *cell.borrow()))
+			.collect();
+
+		// Check every source and target before writing anything.
+		let mut targets = BTreeSet::new();
+		for &(address, _) in &sources {
+			let target = (self.mapping)(address);
+			if !data.valid_address(target) {
+				return Err(Error::InvalidAddress(target));
+			}
+			if !targets.insert(target) {
+				return Err(Error::AddressInUse(target));
+			}
+			if data.is_locked(address) {
+				return Err(Error::SlotLocked(address));
+			}
+			if data.is_locked(target) {
+				return Err(Error::SlotLocked(target));
+			}
+		}
+
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for &(address, expr) in &sources {
+				set_target(data, (self.mapping)(address), expr, undo)?;
+			}
+
+			// Remove the vacated cells.
+			for &(address, _) in &sources {
+				if !targets.contains(&address) {
+					remove_target(data, address, undo)?;
+				}
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
@@ -27,16 +27,26 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Local imports.
-use address::Address;
+use address::{Address, Reference};
+use colors::ColorMetric;
 use data::Data;
+use expression::Expression;
 use operation::{
+	remove_target,
+	revert_on_error,
 	set_target,
 	HistoryEntry,
 	OperationInfo,
 	PaletteOperation,
 	Undo,
 };
-use result::Result;
+use result::{Error, Result};
+
+// Non-local imports.
+use color::Color;
+
+// Standard imports.
+use std::collections::HashMap;
 
 
 
@@ -50,6 +60,7 @@ use result::Result;
 ///
 /// ```rust
 /// use palette::*;
+/// use palette::operation::*;
 /// 
 /// let mut pal = Palette::new("Example", Format::Default, true);
 ///
@@ -58,10 +69,12 @@ use result::Result;
 /// assert_eq!(pal.color(Address::new(0, 0, 0)), None);
 /// 
 /// ```
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct InsertCell {
 	/// The location to start placing the colors.
 	location: Option<Address>,
+	/// The group to place the cell in.
+	group: Option<Reference>,
 	/// Whether to overwrite existing cells when generating new ones.
 	overwrite: bool,
 }
@@ -73,6 +86,7 @@ impl InsertCell {
 	pub fn new() -> InsertCell {
 		InsertCell {
 			location: None,
+			group: None,
 			overwrite: false,
 		}
 	}
@@ -83,6 +97,19 @@ impl InsertCell {
 		self
 	}
 
+	/// Confines the cell to the given group. If no location is set, the cell 
+	/// will be placed at the first free address in the group.
+	///
+	/// # Errors
+	///
+	/// Applying the operation returns a `GroupFull` error if there are no free
+	/// addresses in the group, and an `InvalidAddress` error if the location 
+	/// lies outside of the group.
+	pub fn within(mut self, group: Reference) -> InsertCell {
+		self.group = Some(group);
+		self
+	}
+
 	/// Configures the operation to overwrite existing cells when inserted.
 	pub fn overwrite(mut self, overwrite: bool) -> InsertCell {
 		self.overwrite = overwrite;
@@ -93,17 +120,25 @@ impl InsertCell {
 
 impl PaletteOperation for InsertCell {
 	fn info(&self) -> OperationInfo {
-		OperationInfo {
-			name: "Insert Cell",
-			details: Some(format!("{:?}", self))
-		}
+		OperationInfo::new("Insert Cell")
+			.detail("location", self.location
+				.map_or("auto".to_string(), |a| a.to_string()))
+			.detail("overwrite", self.overwrite)
 	}
 
 
 	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
 		// Get starting address.
 		let starting_address = if let Some(address) = self.location {
+			if let Some(ref group) = self.group {
+				if !group.contains(&address) {
+					return Err(Error::InvalidAddress(address));
+				}
+			}
 			address
+		} else if let Some(ref group) = self.group {
+			data.first_free_address_in(group)
+				.ok_or_else(|| Error::GroupFull(group.clone()))?
 		} else {
 			data.first_free_address_after(Default::default())?
 		};
@@ -115,6 +150,11 @@ impl PaletteOperation for InsertCell {
 			self.overwrite,
 			None
 		)?[0];
+		if let Some(ref group) = self.group {
+			if !group.contains(&target) {
+				return Err(Error::GroupFull(group.clone()));
+			}
+		}
 
 		// Set target.
 		let mut undo = Undo::new_for(self);
@@ -129,6 +169,439 @@ impl PaletteOperation for InsertCell {
 
 
 
+////////////////////////////////////////////////////////////////////////////////
+// InsertColor
+////////////////////////////////////////////////////////////////////////////////
+/// Inserts a new `Color` into the palette.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+///
+/// pal.apply(Box::new(InsertColor::new(Color::new(12, 50, 78)))).unwrap();
+///
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), Some(Color::new(12, 50, 78)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct InsertColor {
+	/// The color to insert.
+	color: Color,
+	/// The location to place the color.
+	location: Option<Address>,
+	/// The group to place the color in.
+	group: Option<Reference>,
+	/// Whether to overwrite an existing cell when inserting the color.
+	overwrite: bool,
+	/// The minimum allowed distance to the existing colors in the palette.
+	min_distance: Option<(u8, ColorMetric)>,
+}
+
+
+impl InsertColor {
+	/// Creates a new InsertColor operation.
+	#[inline]
+	pub fn new(color: Color) -> InsertColor {
+		InsertColor {
+			color: color,
+			location: None,
+			group: None,
+			overwrite: false,
+			min_distance: None,
+		}
+	}
+
+	/// Creates a new InsertColor operation which places the color in the first
+	/// free address in the given group.
+	///
+	/// # Errors
+	///
+	/// Applying the operation returns a `GroupFull` error if there are no free
+	/// addresses in the group.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	///
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(10, 10, 10))
+	/// 	.located_at(Address::new(0, 2, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(20, 20, 20))
+	/// 	.located_at(Address::new(0, 2, 2)))).unwrap();
+	///
+	/// let line = Reference::line_of(&Address::new(0, 2, 0));
+	/// pal.apply(Box::new(InsertColor::into_group(Color::new(30, 30, 30), line)))
+	/// 	.unwrap();
+	///
+	/// assert_eq!(pal.color(Address::new(0, 2, 1)), Some(Color::new(30, 30, 30)));
+	/// ```
+	#[inline]
+	pub fn into_group(color: Color, group: Reference) -> InsertColor {
+		InsertColor::new(color).within(group)
+	}
+
+	/// Sets the location to place the color.
+	pub fn located_at(mut self, location: Address) -> InsertColor {
+		self.location = Some(location);
+		self
+	}
+
+	/// Confines the color to the given group. If no location is set, the color
+	/// will be placed at the first free address in the group.
+	///
+	/// # Errors
+	///
+	/// Applying the operation returns a `GroupFull` error if there are no free
+	/// addresses in the group, and an `InvalidAddress` error if the location 
+	/// lies outside of the group.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(10, 10, 10))
+	/// 	.located_at(Address::new(0, 2, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(20, 20, 20))
+	/// 	.located_at(Address::new(0, 2, 2)))).unwrap();
+	///
+	/// let line = Reference::line_of(&Address::new(0, 2, 0));
+	/// pal.apply(Box::new(InsertColor::new(Color::new(30, 30, 30))
+	/// 	.within(line.clone()))).unwrap();
+	///
+	/// assert_eq!(pal.color(Address::new(0, 2, 1)), Some(Color::new(30, 30, 30)));
+	///
+	/// assert!(pal.apply(Box::new(InsertColor::new(Color::new(40, 40, 40))
+	/// 	.located_at(Address::new(0, 3, 0))
+	/// 	.within(line))).is_err());
+	/// ```
+	pub fn within(mut self, group: Reference) -> InsertColor {
+		self.group = Some(group);
+		self
+	}
+
+	/// Configures the operation to overwrite an existing cell when inserted.
+	pub fn overwrite(mut self, overwrite: bool) -> InsertColor {
+		self.overwrite = overwrite;
+		self
+	}
+
+	/// Configures the operation to fail if the color lies within the given
+	/// distance of any color in the palette, as measured by the given 
+	/// `ColorMetric`.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::colors::ColorMetric;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(12, 50, 78)))).unwrap();
+	///
+	/// assert!(pal.apply(Box::new(InsertColor::new(Color::new(14, 50, 78))
+	/// 	.min_distance(4, ColorMetric::Euclidean))).is_err());
+	/// assert!(pal.apply(Box::new(InsertColor::new(Color::new(200, 50, 78))
+	/// 	.min_distance(4, ColorMetric::Euclidean))).is_ok());
+	/// ```
+	pub fn min_distance(mut self, distance: u8, metric: ColorMetric) 
+		-> InsertColor 
+	{
+		self.min_distance = Some((distance, metric));
+		self
+	}
+}
+
+
+impl PaletteOperation for InsertColor {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Insert Color")
+			.detail("color", self.color)
+			.detail("location", self.location
+				.map_or("auto".to_string(), |a| a.to_string()))
+			.detail("overwrite", self.overwrite)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		// Get starting address.
+		let starting_address = if let Some(address) = self.location {
+			if let Some(ref group) = self.group {
+				if !group.contains(&address) {
+					return Err(Error::InvalidAddress(address));
+				}
+			}
+			address
+		} else if let Some(ref group) = self.group {
+			data.first_free_address_in(group)
+				.ok_or_else(|| Error::GroupFull(group.clone()))?
+		} else {
+			data.first_free_address_after(Default::default())?
+		};
+
+		// Get targets.
+		let target = data.find_targets(
+			1, 
+			starting_address,
+			self.overwrite,
+			None
+		)?[0];
+		if let Some(ref group) = self.group {
+			if !group.contains(&target) {
+				return Err(Error::GroupFull(group.clone()));
+			}
+		}
+
+		// Check distance to existing colors.
+		if let Some((distance, metric)) = self.min_distance {
+			for (&address, cell) in &data.cells {
+				if address == target { continue; }
+				if let Some(color) = cell.color() {
+					if metric.distance(color, self.color) <= distance as f32 {
+						return Err(Error::ColorTooClose(address));
+					}
+				}
+			}
+		}
+
+		// Set target.
+		let mut undo = Undo::new_for(self);
+		set_target(data, target, Expression::Color(self.color), &mut undo)?;
+		data.push_recent_color(self.color);
+		
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SetColor
+////////////////////////////////////////////////////////////////////////////////
+/// Sets the color of an existing cell in the palette.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(12, 50, 78)))).unwrap();
+/// pal.apply(Box::new(SetColor::new(
+/// 	Address::new(0, 0, 0), 
+/// 	Color::new(200, 10, 10)))).unwrap();
+///
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), Some(Color::new(200, 10, 10)));
+///
+/// pal.undo().unwrap();
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), Some(Color::new(12, 50, 78)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SetColor {
+	/// The address of the cell to modify.
+	address: Address,
+	/// The color to assign.
+	color: Color,
+}
+
+
+impl SetColor {
+	/// Creates a new SetColor operation.
+	#[inline]
+	pub fn new(address: Address, color: Color) -> SetColor {
+		SetColor {
+			address: address,
+			color: color,
+		}
+	}
+}
+
+
+impl PaletteOperation for SetColor {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Set Color")
+			.detail("address", self.address)
+			.detail("color", self.color)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		if data.cell(self.address).is_none() {
+			return Err(Error::EmptyAddress(self.address));
+		}
+
+		let mut undo = Undo::new_for(self);
+		set_target(data, self.address, Expression::Color(self.color), &mut undo)?;
+		
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SetExpression
+////////////////////////////////////////////////////////////////////////////////
+/// Replaces the `Expression` in a cell of the palette, creating the cell if it
+/// does not exist.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(12, 50, 78)))).unwrap();
+/// pal.apply(Box::new(SetExpression::new(
+/// 	Address::new(0, 0, 0), 
+/// 	Expression::Empty))).unwrap();
+/// pal.apply(Box::new(SetExpression::new(
+/// 	Address::new(0, 0, 1), 
+/// 	Expression::Color(Color::new(200, 10, 10))))).unwrap();
+///
+/// assert_eq!(pal.len(), 2);
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), None);
+/// assert_eq!(pal.color(Address::new(0, 0, 1)), Some(Color::new(200, 10, 10)));
+///
+/// pal.undo().unwrap();
+/// pal.undo().unwrap();
+/// assert_eq!(pal.len(), 1);
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), Some(Color::new(12, 50, 78)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SetExpression {
+	/// The address of the cell to modify.
+	address: Address,
+	/// The expression to assign.
+	expression: Expression,
+}
+
+
+impl SetExpression {
+	/// Creates a new SetExpression operation.
+	#[inline]
+	pub fn new(address: Address, expression: Expression) -> SetExpression {
+		SetExpression {
+			address: address,
+			expression: expression,
+		}
+	}
+}
+
+
+impl PaletteOperation for SetExpression {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Set Expression")
+			.detail("address", self.address)
+			.detail("expression", format!("{:?}", self.expression))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut undo = Undo::new_for(self);
+		set_target(data, self.address, self.expression, &mut undo)?;
+		
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// MergeRoles
+////////////////////////////////////////////////////////////////////////////////
+/// Overlays a set of role colors onto the palette by setting the cell assigned
+/// to each role to the given color, creating the cell if it is empty. Roles 
+/// which are not given keep their current colors, and roles which are not 
+/// assigned in the palette are ignored.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// use std::collections::HashMap;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+/// pal.apply(Box::new(InsertColor::new(Color::new(255, 255, 255)))).unwrap();
+/// pal.set_role("background", Address::new(0, 0, 0));
+/// pal.set_role("text", Address::new(0, 0, 1));
+/// pal.set_role("accent", Address::new(0, 0, 2));
+///
+/// let mut theme = HashMap::new();
+/// theme.insert("text".to_string(), Color::new(230, 230, 200));
+/// theme.insert("accent".to_string(), Color::new(200, 40, 40));
+/// pal.apply(Box::new(MergeRoles::new(theme))).unwrap();
+///
+/// assert_eq!(pal.role_color("background"), Some(Color::new(0, 0, 0)));
+/// assert_eq!(pal.role_color("text"), Some(Color::new(230, 230, 200)));
+/// assert_eq!(pal.role_color("accent"), Some(Color::new(200, 40, 40)));
+///
+/// pal.undo().unwrap();
+/// assert_eq!(pal.role_color("text"), Some(Color::new(255, 255, 255)));
+/// assert_eq!(pal.role_color("accent"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MergeRoles {
+	/// The colors to assign, keyed by role.
+	roles: HashMap<String, Color>,
+}
+
+
+impl MergeRoles {
+	/// Creates a new MergeRoles operation.
+	#[inline]
+	pub fn new(roles: HashMap<String, Color>) -> MergeRoles {
+		MergeRoles {
+			roles: roles,
+		}
+	}
+}
+
+
+impl PaletteOperation for MergeRoles {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Merge Roles")
+			.detail("count", self.roles.len())
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (role, &color) in &self.roles {
+				if let Some(address) = data.role(role) {
+					set_target(data, address, Expression::Color(color), undo)?;
+				}
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
 ////////////////////////////////////////////////////////////////////////////////
 // DeleteCell
 ////////////////////////////////////////////////////////////////////////////////
@@ -138,10 +611,11 @@ impl PaletteOperation for InsertCell {
 ///
 /// ```rust
 /// use palette::*;
+/// use palette::operation::*;
 /// 
 /// let mut pal = Palette::new("Example", Format::Default, true);
 ///
-/// pal.apply(Box::new(InsertCell::new(Color::new(12, 50, 78)))).unwrap();
+/// pal.apply(Box::new(InsertColor::new(Color::new(12, 50, 78)))).unwrap();
 /// pal.apply(Box::new(DeleteCell::new(Address::new(0, 0, 0)))).unwrap();
 /// 
 /// assert_eq!(pal.len(), 0);
@@ -164,16 +638,14 @@ impl DeleteCell {
 
 impl PaletteOperation for DeleteCell {
 	fn info(&self) -> OperationInfo {
-		OperationInfo {
-			name: "Remove Cell",
-			details: Some(format!("{:?}", self))
-		}
+		OperationInfo::new("Remove Cell")
+			.detail("address", self.address)
 	}
 
 	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
 
 		let mut undo = Undo::new_for(self);
-		undo.record(self.address, Some(data.remove_cell(self.address)?));
+		remove_target(data, self.address, &mut undo)?;
 		
 		Ok(HistoryEntry {
 			info: self.info(),
@@ -27,6 +27,7 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Local imports.
+use address::{Address, Selection};
 use data::Data;
 use operation::{
 	HistoryEntry,
@@ -34,6 +35,7 @@ use operation::{
 	PaletteOperation,
 };
 use result::Result;
+use Palette;
 
 // Standard imports.
 use std::mem;
@@ -43,12 +45,14 @@ use std::mem;
 ////////////////////////////////////////////////////////////////////////////////
 // Sequence
 ////////////////////////////////////////////////////////////////////////////////
-/// Applies a sequence of operations to the palette.
+/// Applies a sequence of operations to the palette. If any operation fails, 
+/// the operations already applied are reverted before the error is returned.
 ///
 /// # Example
 ///
 /// ```rust
 /// use palette::*;
+/// use palette::operation::*;
 ///
 /// let mut pal = Palette::new("Example", Format::Default, true);
 ///
@@ -79,10 +83,8 @@ impl Sequence {
 
 impl PaletteOperation for Sequence {
 	fn info(&self) -> OperationInfo {
-		OperationInfo {
-			name: "Sequence",
-			details: Some(format!("{:?}", self))
-		}
+		OperationInfo::new("Sequence")
+			.detail("count", self.operations.len())
 	}
 
 	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
@@ -90,18 +92,123 @@ impl PaletteOperation for Sequence {
 
 		let operations = mem::replace(&mut self.operations, Vec::new());
 		for mut operation in operations {
-			let entry = operation.apply(data)?;
-			undo_sequence.push(entry.undo);
+			match operation.apply(data) {
+				Ok(entry) => {
+					undo_sequence.push(entry.undo);
+					// Keep operations with changes outside of a restriction.
+					if operation.has_pending() {
+						self.operations.push(operation);
+					}
+				},
+				Err(e) => {
+					// Revert the applied operations.
+					while let Some(mut undo) = undo_sequence.pop() {
+						undo.apply(data)?;
+					}
+					return Err(e);
+				},
+			}
 		}
+		// Undo the operations in reverse order.
+		undo_sequence.reverse();
 
 		Ok(HistoryEntry {
 			info: self.info(),
 			undo: Box::new(Sequence::new(undo_sequence)),
 		})
 	}
+
+	fn affected(&self) -> Vec<Address> {
+		let mut affected: Vec<Address> = self.operations
+			.iter()
+			.flat_map(|operation| operation.affected())
+			.collect();
+		affected.sort();
+		affected.dedup();
+		affected
+	}
+
+	fn restrict(&mut self, selection: Option<&Selection>) -> bool {
+		let mut restricted = true;
+		for operation in &mut self.operations {
+			restricted &= operation.restrict(selection);
+		}
+		restricted
+	}
+
+	fn has_pending(&self) -> bool {
+		self.operations.iter().any(|operation| operation.has_pending())
+	}
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Pipeline
+////////////////////////////////////////////////////////////////////////////////
+/// Builds a chain of operations which are applied to a palette as a single 
+/// `Sequence`, so that one undo reverts the entire chain.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+///
+/// let mut pal = Palette::new("Example", Format::Default, true);
+///
+/// Pipeline::new()
+/// 	.then(InsertColor::new(Color::new(10, 10, 10)))
+/// 	.then(InsertColor::new(Color::new(20, 20, 20)))
+/// 	.apply(&mut pal)
+/// 	.unwrap();
+///
+/// assert_eq!(pal.len(), 2);
+/// assert_eq!(pal.history().count(), 1);
+///
+/// pal.undo().unwrap();
+/// assert_eq!(pal.len(), 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct Pipeline {
+	operations: Vec<Box<PaletteOperation>>
+}
+
+
+impl Pipeline {
+	/// Creates a new, empty Pipeline.
+	#[inline]
+	pub fn new() -> Pipeline {
+		Default::default()
+	}
+
+	/// Appends the given operation to the pipeline.
+	pub fn then<O>(mut self, operation: O) -> Pipeline
+		where O: PaletteOperation + 'static
+	{
+		self.operations.push(Box::new(operation));
+		self
+	}
+
+	/// Returns the number of operations in the pipeline.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.operations.len()
+	}
+
+	/// Returns whether the pipeline contains no operations.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.operations.is_empty()
+	}
+
+	/// Applies the operations to the given palette as a single `Sequence`.
+	pub fn apply(self, palette: &mut Palette) -> Result<()> {
+		palette.apply(Box::new(Sequence::new(self.operations)))
+	}
 }
 
 
+
 ////////////////////////////////////////////////////////////////////////////////
 // Repeat
 ////////////////////////////////////////////////////////////////////////////////
@@ -111,6 +218,7 @@ impl PaletteOperation for Sequence {
 ///
 /// ```rust
 /// use palette::*;
+/// use palette::operation::*;
 ///
 /// let mut pal = Palette::new("Example", Format::Default, true);
 ///
@@ -152,19 +260,28 @@ impl Repeat {
 
 impl PaletteOperation for Repeat {
 	fn info(&self) -> OperationInfo {
-		OperationInfo {
-			name: "Repeat",
-			details: Some(format!("{:?}", self))
-		}
+		OperationInfo::new("Repeat")
+			.detail("operation", self.operation.info().name)
+			.detail("count", self.repeat_count)
 	}
 
 	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
 		let mut undo_sequence: Vec<Box<PaletteOperation>> = Vec::new();
 
 		for _ in 0..self.repeat_count {
-			let entry = self.operation.apply(data)?;
-			undo_sequence.push(entry.undo);
+			match self.operation.apply(data) {
+				Ok(entry) => undo_sequence.push(entry.undo),
+				Err(e) => {
+					// Revert the applied repetitions.
+					while let Some(mut undo) = undo_sequence.pop() {
+						undo.apply(data)?;
+					}
+					return Err(e);
+				},
+			}
 		}
+		// Undo the operations in reverse order.
+		undo_sequence.reverse();
 
 		Ok(HistoryEntry {
 			info: self.info(),
@@ -0,0 +1,291 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines operations for converting a palette between formats.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use address::{Address, Reference};
+use data::Data;
+use expression::Expression;
+use format::Format;
+use operation::{
+	HistoryEntry,
+	OperationInfo,
+	PaletteOperation,
+};
+use result::{Error, Result};
+
+// Standard imports.
+use std::collections::BTreeMap;
+use std::mem;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RestoreData
+////////////////////////////////////////////////////////////////////////////////
+/// Replaces the palette data with a saved copy. Used to undo operations which 
+/// change more than the palette's cells.
+#[derive(Debug)]
+struct RestoreData {
+	/// Information about the operation being undone.
+	undoing: OperationInfo,
+	/// The data to restore.
+	snapshot: Data,
+	/// The addresses occupied before or after the restore.
+	affected: Vec<Address>,
+}
+
+
+impl PaletteOperation for RestoreData {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Restore Data")
+			.detail("undoing", self.undoing.name)
+	}
+
+	fn affected(&self) -> Vec<Address> {
+		self.affected.clone()
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let snapshot = mem::replace(&mut self.snapshot, Default::default());
+		let previous = data.clone();
+		data.restore(snapshot);
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(RestoreData {
+				undoing: self.undoing.clone(),
+				snapshot: previous,
+				affected: self.affected.clone(),
+			}),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ConvertFormat
+////////////////////////////////////////////////////////////////////////////////
+/// Converts the palette to the layout and constraints of the given `Format`. 
+/// The colors are placed in address order starting from the first address of 
+/// the converted palette. Empty cells are dropped, and locked cells remain 
+/// locked at their new addresses. Names, roles, and reserved groups follow the
+/// first cell moved out of their group, and the recent colors and color budget
+/// are kept. The palette's format is changed along with its data, so undoing 
+/// the conversion restores the original format.
+///
+/// # Errors
+///
+/// Returns a `FormatCapacityExceeded` error giving the number of colors and 
+/// the capacity of the format if the colors do not fit in the given format.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// for i in 0..20 {
+/// 	pal.apply(Box::new(InsertColor::new(Color::new(i, i, i)))).unwrap();
+/// }
+/// assert_eq!(pal.color(Address::new(0, 0, 19)), Some(Color::new(19, 19, 19)));
+/// pal.set_role("highlight", Address::new(0, 0, 19));
+///
+/// pal.apply(Box::new(ConvertFormat::new(Format::Zpl))).unwrap();
+/// assert_eq!(pal.format(), Format::Zpl);
+/// assert_eq!(pal.len(), 20);
+/// assert_eq!(pal.color(Address::new(0, 0, 19)), None);
+/// assert_eq!(pal.color(Address::new(0, 1, 3)), Some(Color::new(19, 19, 19)));
+/// assert_eq!(pal.role_color("highlight"), Some(Color::new(19, 19, 19)));
+/// assert_eq!(pal.name(&Reference::all()), Some("Example"));
+/// assert!(pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0))
+/// 	.located_at(Address::new(600, 0, 0)))).is_err());
+///
+/// pal.undo().unwrap();
+/// assert_eq!(pal.format(), Format::Default);
+/// assert_eq!(pal.color(Address::new(0, 0, 19)), Some(Color::new(19, 19, 19)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConvertFormat {
+	/// The format to convert to.
+	format: Format,
+}
+
+
+impl ConvertFormat {
+	/// Creates a new ConvertFormat operation.
+	#[inline]
+	pub fn new(format: Format) -> ConvertFormat {
+		ConvertFormat {
+			format: format,
+		}
+	}
+}
+
+
+impl PaletteOperation for ConvertFormat {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Convert Format")
+			.detail("format", format!("{:?}", self.format))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut converted: Data = Default::default();
+		self.format.initialize(&mut converted);
+		converted.set_color_budget(data.color_budget());
+
+		// Place the colors in address order.
+		let sources: Vec<(Address, Expression)> = data.cells
+			.iter()
+			.filter(|&(_, cell)| cell.color().is_some())
+			.map(|(&address, cell)| (address, *cell.borrow()))
+			.collect();
+		let targets = match converted.find_targets(
+			sources.len(), 
+			Address::default(), 
+			true, 
+			None)
+		{
+			Ok(targets) => targets,
+			Err(Error::MaxCellLimitExceeded) => return Err(
+				Error::FormatCapacityExceeded(
+					sources.len(), 
+					capacity(&mut converted)?)),
+			Err(e) => return Err(e),
+		};
+		let mut moved = BTreeMap::new();
+		for (&target, &(source, expr)) in targets.iter().zip(sources.iter()) {
+			*converted.create_cell(target)?.borrow_mut() = expr;
+			if data.is_locked(source) {
+				converted.set_locked(target, true);
+			}
+			moved.insert(source, target);
+		}
+
+		// Carry the names, roles, and reservations to the new addresses.
+		for (group, meta) in &data.metadata {
+			if let Some(ref name) = meta.name {
+				if let Some(group) = remap_group(group, &moved, &mut converted) {
+					converted.set_name(group, name.clone());
+				}
+			}
+		}
+		for (name, group) in &data.names {
+			if let Some(group) = remap_group(group, &moved, &mut converted) {
+				converted.names.insert(name.clone(), group);
+			}
+		}
+		for (role, address) in &data.roles {
+			if let Some(&target) = moved.get(address) {
+				converted.set_role(role.clone(), target);
+			}
+		}
+		for group in data.reserved() {
+			if let Some(group) = remap_group(group, &moved, &mut converted) {
+				converted.reserve(group);
+			}
+		}
+		converted.set_recent_capacity(data.recent_capacity());
+		for &color in data.recent_colors() {
+			converted.push_recent_color(color);
+		}
+
+		let mut affected: Vec<Address> = data.cells.keys()
+			.chain(converted.cells.keys())
+			.cloned()
+			.collect();
+		affected.sort();
+		affected.dedup();
+
+		let previous = data.clone();
+		data.restore(converted);
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(RestoreData {
+				undoing: self.info(),
+				snapshot: previous,
+				affected: affected,
+			}),
+		})
+	}
+}
+
+
+/// Returns the number of addresses available in the given data.
+fn capacity(data: &mut Data) -> Result<usize> {
+	let mut count = 0;
+	let mut next = Address::default();
+	loop {
+		data.prepare_address(next)?;
+		count += 1;
+		next = next.wrapping_step(
+			1,
+			data.maximum_page_count,
+			data.line_count(&Reference::page_of(&next)),
+			data.column_count(&Reference::line_of(&next)),
+		);
+		if next == Address::default() { return Ok(count); }
+	}
+}
+
+
+/// Returns the group in the converted data corresponding to the given group. 
+/// A group follows the first cell moved out of it, and a group with no moved 
+/// cells keeps its place if it exists in the converted layout. Returns None if
+/// the group has no counterpart.
+fn remap_group(
+	group: &Reference, 
+	moved: &BTreeMap<Address, Address>,
+	converted: &mut Data)
+	-> Option<Reference>
+{
+	if *group == Reference::all() { return Some(group.clone()); }
+	let page = group.page().ok()?;
+
+	let address = match moved.iter().find(|&(source, _)| group.contains(source)) {
+		Some((_, &target)) => target,
+		None => {
+			let address = Address::new(
+				page, 
+				group.line().unwrap_or(0), 
+				group.column().unwrap_or(0));
+			if !converted.valid_address(address) { return None; }
+			converted.prepare_address(address).ok()?;
+			address
+		},
+	};
+
+	match (group.line().is_ok(), group.column().is_ok()) {
+		(true, true)   => Some(Reference::from(address)),
+		(true, false)  => Some(Reference::line_of(&address)),
+		(false, false) => Some(Reference::page_of(&address)),
+		(false, true)  => None,
+	}
+}
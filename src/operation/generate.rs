@@ -0,0 +1,469 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines operations which generate new colors for the palette.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use address::{Address, Reference};
+use colors;
+use data::Data;
+use expression::Expression;
+use operation::{
+	remove_target,
+	revert_on_error,
+	set_target,
+	HistoryEntry,
+	OperationInfo,
+	PaletteOperation,
+	Undo,
+};
+use result::{Error, Result};
+use utilities::clamped;
+
+// Non-local imports.
+use color::Color;
+
+
+
+/// The maximum number of iterations to refine the k-means clusters.
+const K_MEANS_ITERATIONS: usize = 32;
+
+
+/// Returns the squared distance between the given points.
+fn distance_squared(a: [f32; 3], b: [f32; 3]) -> f32 {
+	(a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+/// Returns the index of the centroid nearest to the given point.
+fn nearest_centroid(point: [f32; 3], centroids: &[[f32; 3]]) -> usize {
+	let mut nearest = 0;
+	for (i, &centroid) in centroids.iter().enumerate() {
+		if distance_squared(point, centroid) 
+			< distance_squared(point, centroids[nearest]) 
+		{
+			nearest = i;
+		}
+	}
+	nearest
+}
+
+/// Returns up to `count` cluster centroids for the given weighted points, 
+/// computed using weighted k-means. The centroids are seeded deterministically
+/// by choosing the heaviest point, then repeatedly choosing the point with the
+/// greatest weighted distance from the chosen centroids.
+fn weighted_k_means(points: &[([f32; 3], f32)], count: usize) -> Vec<[f32; 3]> {
+	let mut centroids: Vec<[f32; 3]> = Vec::with_capacity(count);
+
+	// Seed the centroids.
+	while centroids.len() < count {
+		let mut best = None;
+		let mut best_score = 0.0;
+		for &(point, weight) in points {
+			let score = if centroids.is_empty() {
+				weight
+			} else {
+				let nearest = centroids[nearest_centroid(point, &centroids)];
+				weight * distance_squared(point, nearest)
+			};
+			if score > best_score {
+				best = Some(point);
+				best_score = score;
+			}
+		}
+		match best {
+			Some(point) => centroids.push(point),
+			None => break, // All remaining points coincide with a centroid.
+		}
+	}
+
+	// Refine the centroids.
+	for _ in 0..K_MEANS_ITERATIONS {
+		let mut sums = vec![([0.0f32; 3], 0.0f32); centroids.len()];
+		for &(point, weight) in points {
+			let sum = &mut sums[nearest_centroid(point, &centroids)];
+			for (s, p) in sum.0.iter_mut().zip(point.iter()) {
+				*s += p * weight;
+			}
+			sum.1 += weight;
+		}
+
+		let mut changed = false;
+		for (centroid, &(sum, weight)) in centroids.iter_mut().zip(sums.iter()) {
+			if weight <= 0.0 { continue; }
+			let mean = [sum[0] / weight, sum[1] / weight, sum[2] / weight];
+			if distance_squared(mean, *centroid) > 0.0 {
+				*centroid = mean;
+				changed = true;
+			}
+		}
+		if !changed { break; }
+	}
+
+	centroids
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// FitToHistogram
+////////////////////////////////////////////////////////////////////////////////
+/// Replaces the colors in the palette with colors fitted to a color histogram 
+/// using weighted k-means clustering. The fitted colors are placed starting at
+/// the first address, and any remaining cells are removed.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::colors::ColorMetric;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(FitToHistogram::new(vec![
+/// 	(Color::new(200, 10, 10), 1000),
+/// 	(Color::new(10, 10, 200), 1),
+/// 	(Color::new(10, 200, 10), 1),
+/// ], 2))).unwrap();
+///
+/// assert_eq!(pal.len(), 2);
+/// let dominant = pal.color(Address::new(0, 0, 0)).unwrap();
+/// assert!(ColorMetric::Euclidean.distance(dominant, Color::new(200, 10, 10))
+/// 	< 2.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FitToHistogram {
+	/// The colors to fit and their frequencies.
+	histogram: Vec<(Color, u32)>,
+	/// The number of colors to generate.
+	count: usize,
+}
+
+
+impl FitToHistogram {
+	/// Creates a new FitToHistogram operation generating `count` colors.
+	#[inline]
+	pub fn new(histogram: Vec<(Color, u32)>, count: usize) -> FitToHistogram {
+		FitToHistogram {
+			histogram: histogram,
+			count: count,
+		}
+	}
+}
+
+
+impl PaletteOperation for FitToHistogram {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Fit To Histogram")
+			.detail("histogram size", self.histogram.len())
+			.detail("count", self.count)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let points: Vec<([f32; 3], f32)> = self.histogram
+			.iter()
+			.map(|&(color, frequency)| (colors::ratios(color), frequency as f32))
+			.collect();
+		let centroids = weighted_k_means(&points, self.count);
+
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			// Place the fitted colors.
+			let targets = data.find_targets(
+				centroids.len(), 
+				Address::default(), 
+				true, 
+				None
+			)?;
+			for (&target, &centroid) in targets.iter().zip(centroids.iter()) {
+				let color = colors::from_ratios(centroid);
+				set_target(data, target, Expression::Color(color), undo)?;
+			}
+
+			// Remove the remaining cells.
+			let remaining: Vec<Address> = data.cells
+				.keys()
+				.filter(|address| !targets.contains(address))
+				.cloned()
+				.collect();
+			for address in remaining {
+				remove_target(data, address, undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DitherPattern
+////////////////////////////////////////////////////////////////////////////////
+/// The pattern in which two colors are arranged by `GenerateDither`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DitherPattern {
+	/// Alternates the colors along both lines and columns.
+	Checkerboard,
+	/// Alternates the colors between lines.
+	Horizontal,
+	/// Alternates the colors between columns.
+	Vertical,
+	/// Uses the 2x2 Bayer matrix to place the second color in the given number 
+	/// of cells, between 0 and 4, of each 2x2 block.
+	Bayer2x2(u8),
+}
+
+
+impl DitherPattern {
+	/// Returns whether the second color should be placed at the given line and
+	/// column.
+	fn second(self, line: usize, column: usize) -> bool {
+		match self {
+			DitherPattern::Checkerboard => (line + column) % 2 == 1,
+			DitherPattern::Horizontal => line % 2 == 1,
+			DitherPattern::Vertical => column % 2 == 1,
+			DitherPattern::Bayer2x2(level) => {
+				const BAYER: [[u8; 2]; 2] = [[0, 2], [3, 1]];
+				BAYER[line % 2][column % 2] < level
+			},
+		}
+	}
+}
+
+
+impl Default for DitherPattern {
+	fn default() -> Self {
+		DitherPattern::Checkerboard
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GenerateDither
+////////////////////////////////////////////////////////////////////////////////
+/// Fills every address in a group with two colors arranged in a 
+/// `DitherPattern`, overwriting any existing cells.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Zpl, true);
+/// let (a, b) = (Color::new(0, 0, 0), Color::new(255, 255, 255));
+/// let page = Reference::page_of(&Address::new(0, 0, 0));
+///
+/// pal.apply(Box::new(GenerateDither::new(
+/// 	page, a, b, DitherPattern::Checkerboard))).unwrap();
+///
+/// assert_eq!(pal.len(), 14 * 16);
+/// for line in 0..14 {
+/// 	for column in 0..16 {
+/// 		let expected = if (line + column) % 2 == 0 {a} else {b};
+/// 		assert_eq!(pal.color(Address::new(0, line, column)), Some(expected));
+/// 	}
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenerateDither {
+	/// The group to fill.
+	group: Reference,
+	/// The first color of the pattern.
+	color_a: Color,
+	/// The second color of the pattern.
+	color_b: Color,
+	/// The arrangement of the colors.
+	pattern: DitherPattern,
+}
+
+
+impl GenerateDither {
+	/// Creates a new GenerateDither operation.
+	#[inline]
+	pub fn new(
+		group: Reference, 
+		color_a: Color, 
+		color_b: Color, 
+		pattern: DitherPattern) 
+		-> GenerateDither
+	{
+		GenerateDither {
+			group: group,
+			color_a: color_a,
+			color_b: color_b,
+			pattern: pattern,
+		}
+	}
+}
+
+
+impl PaletteOperation for GenerateDither {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Generate Dither")
+			.detail("group", &self.group)
+			.detail("pattern", format!("{:?}", self.pattern))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let pages = match self.group.page() {
+			Ok(page) => page..page.saturating_add(1),
+			Err(_) => 0..data.maximum_page_count,
+		};
+
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for page in pages {
+				let first = Address::new(page, 0, 0);
+				data.prepare_address(first)?;
+				let lines = match self.group.line() {
+					Ok(line) => line..line.saturating_add(1),
+					Err(_) => 0..data.line_count(&Reference::page_of(&first)),
+				};
+
+				for line in lines {
+					let line_start = Address::new(page, line, 0);
+					data.prepare_address(line_start)?;
+					let line_group = Reference::line_of(&line_start);
+					let columns = match self.group.column() {
+						Ok(column) => column..column.saturating_add(1),
+						Err(_) => 0..data.column_count(&line_group),
+					};
+
+					for column in columns {
+						let address = Address::new(page, line, column);
+						let second = self.pattern.second(
+							line as usize, 
+							column as usize);
+						let color = if second {self.color_b} else {self.color_a};
+						set_target(data, address, Expression::Color(color), undo)?;
+					}
+				}
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// GenerateVariants
+////////////////////////////////////////////////////////////////////////////////
+/// Inserts a lighter and a darker variant of the color of a cell, adjusting
+/// its HSL lightness by the given amounts. The variants are placed in the first
+/// free addresses after the base cell, lighter first.
+///
+/// The variants are computed when the operation is applied, so they will not 
+/// follow later changes to the base cell.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::colors::hsl;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(40, 80, 160)))).unwrap();
+/// pal.apply(Box::new(GenerateVariants::new(
+/// 	Address::new(0, 0, 0), 
+/// 	0.2, 
+/// 	0.2))).unwrap();
+///
+/// let [_, _, base] = hsl(pal.color(Address::new(0, 0, 0)).unwrap());
+/// let [_, _, light] = hsl(pal.color(Address::new(0, 0, 1)).unwrap());
+/// let [_, _, dark] = hsl(pal.color(Address::new(0, 0, 2)).unwrap());
+/// assert!(light > base);
+/// assert!(dark < base);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GenerateVariants {
+	/// The address of the base color.
+	base: Address,
+	/// The amount to increase the lightness of the lighter variant by.
+	lighten: f32,
+	/// The amount to decrease the lightness of the darker variant by.
+	darken: f32,
+}
+
+
+impl GenerateVariants {
+	/// Creates a new GenerateVariants operation.
+	#[inline]
+	pub fn new(base: Address, lighten: f32, darken: f32) -> GenerateVariants {
+		GenerateVariants {
+			base: base,
+			lighten: lighten,
+			darken: darken,
+		}
+	}
+}
+
+
+impl PaletteOperation for GenerateVariants {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Generate Variants")
+			.detail("base", self.base)
+			.detail("lighten", self.lighten)
+			.detail("darken", self.darken)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let color = data.cell(self.base)
+			.and_then(|cell| cell.color())
+			.ok_or(Error::EmptyAddress(self.base))?;
+		let [h, s, l] = colors::hsl(color);
+		let light = colors::from_hsl([h, s, clamped(l + self.lighten, 0.0, 1.0)]);
+		let dark = colors::from_hsl([h, s, clamped(l - self.darken, 0.0, 1.0)]);
+
+		let start = data.first_free_address_after(self.base)?;
+		let targets = data.find_targets(2, start, false, None)?;
+
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (&target, &variant) in targets.iter().zip([light, dark].iter()) {
+				set_target(data, target, Expression::Color(variant), undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
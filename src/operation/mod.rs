@@ -30,34 +30,82 @@
 
 // Sumbodules.
 #[warn(missing_docs)]
+mod arrange;
+#[warn(missing_docs)]
 mod basic;
 #[warn(missing_docs)]
 mod combine;
 #[warn(missing_docs)]
+mod convert;
+#[warn(missing_docs)]
+mod generate;
+#[warn(missing_docs)]
+mod ramp;
+#[warn(missing_docs)]
+mod transform;
+#[warn(missing_docs)]
 mod undo;
 
 // Submodule re-exports.
+pub use self::arrange::{
+	CopyCells,
+	DuplicatePage,
+	InterleaveRamps,
+	Remap,
+	ScanOrder,
+	SortKey,
+	SortPalette,
+};
 pub use self::basic::{
 	InsertCell,
+	InsertColor,
+	MergeRoles,
+	SetColor,
+	SetExpression,
 	DeleteCell,
 };
 pub use self::combine::{
+	Pipeline,
 	Repeat,
 	Sequence,
 };
+pub use self::convert::ConvertFormat;
+pub use self::generate::{
+	DitherPattern,
+	FitToHistogram,
+	GenerateDither,
+	GenerateVariants,
+};
+pub use self::ramp::{
+	InsertRamp,
+	RampLayout,
+};
+pub use self::transform::{
+	ApplyMatrix,
+	HueMode,
+	MapColors,
+	Recolor,
+	RotateHue,
+	SnapToPalette,
+};
 pub use self::undo::Undo;
 
 // Local imports.
-use address::Address;
+use address::{Address, Selection};
 use cell::Cell;
 use data::Data;
 use expression::Expression;
 use result::{Error, Result};
 
+// Non-local imports.
+use color::Color;
+
 // Standard imports.
+use std::collections::BTreeMap;
 use std::fmt;
 use std::rc::{Rc, Weak};
 use std::mem;
+use std::time::{Duration, Instant};
 
 
 /// Returns a weak reference to the source element located at the given address 
@@ -100,7 +148,8 @@ pub(crate) fn target(
 }
 
 /// Stores the given Expression in the cell at the given address in the given 
-/// palette. If the cell is empty, it will be created.
+/// palette. If the cell is empty, it will be created. No change is recorded if
+/// the cell already holds an equal Expression.
 pub(crate) fn set_target(
 	data: &mut Data,
 	address: Address,
@@ -108,15 +157,85 @@ pub(crate) fn set_target(
 	undo: &mut Undo)
 	-> Result<()>
 {
+	if data.is_locked(address) {
+		return Err(Error::SlotLocked(address));
+	}
+
+	if let Expression::Color(color) = new_element {
+		if !data.within_color_budget(address, color) {
+			return Err(Error::BudgetExceeded(data.color_budget().unwrap_or(0)));
+		}
+	}
+
 	// Get the target cell.
 	let target = target(data, address, undo)?;
 
+	// Skip no-op changes.
+	if *target.borrow() == new_element {
+		return Ok(());
+	}
+
 	// Insert new element into palette.
 	let cur = mem::replace(&mut *target.borrow_mut(), new_element);
 	undo.record(address, Some(cur));
 	Ok(())
 }
 
+/// Removes the cell at the given address from the given palette.
+pub(crate) fn remove_target(
+	data: &mut Data,
+	address: Address,
+	undo: &mut Undo)
+	-> Result<()>
+{
+	if data.is_locked(address) {
+		return Err(Error::SlotLocked(address));
+	}
+	undo.record(address, Some(data.remove_cell(address)?));
+	Ok(())
+}
+
+/// Calls the given function to modify the given palette data, recording its 
+/// changes in the given `Undo`. If the function fails, the changes it made are
+/// reverted before its error is returned, so that a failed operation leaves 
+/// the palette unchanged.
+pub(crate) fn revert_on_error<F>(data: &mut Data, undo: &mut Undo, write: F) 
+	-> Result<()>
+	where F: FnOnce(&mut Data, &mut Undo) -> Result<()>
+{
+	match write(data, undo) {
+		Ok(()) => Ok(()),
+		Err(e) => {
+			undo.apply(data)?;
+			Err(e)
+		},
+	}
+}
+
+
+/// Returns the addresses and colors of the cells in the given selection which
+/// resolve to a color, in address order.
+pub(crate) fn selected_colors(data: &Data, selection: &Selection) 
+	-> Vec<(Address, Color)>
+{
+	data.cells
+		.iter()
+		.filter(|&(address, _)| selection.contains(address))
+		.filter_map(|(&address, cell)| cell.color().map(|c| (address, c)))
+		.collect()
+}
+
+/// Returns the addresses and colors of the cells in the given selection which 
+/// resolve to a color and are not locked, in address order.
+pub(crate) fn unlocked_colors(data: &Data, selection: &Selection) 
+	-> Vec<(Address, Color)>
+{
+	selected_colors(data, selection)
+		.into_iter()
+		.filter(|&(address, _)| !data.is_locked(address))
+		.collect()
+}
+
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -130,6 +249,28 @@ pub trait PaletteOperation: fmt::Debug {
 	/// Applies the operation to the given palette.
 	fn apply(&mut self, data: &mut Data) 
 		-> Result<HistoryEntry>;
+
+	/// Returns the addresses which applying the operation will modify, if they
+	/// are known before it is applied. Undo operations use this to report the 
+	/// addresses changed by the operations they reverse.
+	fn affected(&self) -> Vec<Address> {
+		Vec::new()
+	}
+
+	/// Restricts the changes made by applying the operation to the given 
+	/// selection, or lifts the restriction if the selection is None. Returns 
+	/// false if the operation cannot be restricted. Undo operations use this 
+	/// to revert part of an operation.
+	#[allow(unused_variables)]
+	fn restrict(&mut self, selection: Option<&Selection>) -> bool {
+		false
+	}
+
+	/// Returns whether the operation has changes left to make after being 
+	/// applied under a restriction.
+	fn has_pending(&self) -> bool {
+		false
+	}
 }
 
 
@@ -145,6 +286,63 @@ pub struct OperationHistory {
 	pub undo_entries: Vec<HistoryEntry>,
 	/// The record of available redos.
 	pub redo_entries: Vec<HistoryEntry>,
+	/// The time window within which consecutive operations on the same target
+	/// are merged into a single undo entry, or None if they are never merged.
+	pub coalesce_window: Option<Duration>,
+	/// The time the most recent undo entry was recorded, or None if the next
+	/// entry should not be merged into it.
+	last_recorded: Option<Instant>,
+}
+
+
+impl OperationHistory {
+	/// Records the given entry as the most recent undo entry and clears the
+	/// redo entries. If the entry's operation has the same name and modified 
+	/// the same addresses as the previous entry's and is recorded within the 
+	/// coalesce window, the two entries are merged.
+	pub fn record(&mut self, entry: HistoryEntry) {
+		let now = Instant::now();
+		let coalesce = match (self.coalesce_window, self.last_recorded) {
+			(Some(window), Some(last)) => now.duration_since(last) <= window,
+			_ => false,
+		};
+
+		let entry = match self.undo_entries.pop() {
+			Some(last) => if coalesce && same_target(&last, &entry) {
+				HistoryEntry {
+					info: entry.info,
+					undo: Box::new(Sequence::new(vec![entry.undo, last.undo])),
+				}
+			} else {
+				self.undo_entries.push(last);
+				entry
+			},
+			None => entry,
+		};
+
+		self.undo_entries.push(entry);
+		self.redo_entries.clear();
+		self.last_recorded = Some(now);
+	}
+
+	/// Prevents the next recorded entry from being merged into the most recent
+	/// entry.
+	pub fn commit(&mut self) {
+		self.last_recorded = None;
+	}
+}
+
+
+/// Returns whether the given entries' operations have the same name and 
+/// modified the same addresses. Entries whose affected addresses are unknown 
+/// never have the same target.
+fn same_target(a: &HistoryEntry, b: &HistoryEntry) -> bool {
+	if a.info.name != b.info.name { return false; }
+	let a_affected = a.affected();
+	let b_affected = b.affected();
+	!a_affected.is_empty() && 
+	!b_affected.is_empty() && 
+	a_affected == b_affected
 }
 
 
@@ -163,17 +361,60 @@ pub struct HistoryEntry {
 }
 
 
+impl HistoryEntry {
+	/// Returns the addresses modified by the applied operation, in address 
+	/// order.
+	pub fn affected(&self) -> Vec<Address> {
+		self.undo.affected()
+	}
+}
+
+
 
 ////////////////////////////////////////////////////////////////////////////////
 // OperationInfo
 ////////////////////////////////////////////////////////////////////////////////
 /// Describes an applied operation.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+///
+/// let op = Repeat::new(Box::new(InsertCell::new())).repeat(3);
+/// let info = op.info();
+///
+/// assert_eq!(info.name, "Repeat");
+/// assert_eq!(info.details["count"], "3");
+/// ```
 #[derive(Debug, PartialOrd, PartialEq, Eq, Hash, Ord, Clone)]
 pub struct OperationInfo {
 	/// The name of the operation.
 	pub name: &'static str,
-	/// The details of the operation.
-	pub details: Option<String>,
+	/// The details of the operation, keyed by field name.
+	pub details: BTreeMap<&'static str, String>,
+}
+
+
+impl OperationInfo {
+	/// Creates a new `OperationInfo` with the given name and no details.
+	#[inline]
+	pub fn new(name: &'static str) -> OperationInfo {
+		OperationInfo {
+			name: name,
+			details: BTreeMap::new(),
+		}
+	}
+
+	/// Adds a detail to the `OperationInfo`.
+	#[inline]
+	pub fn detail<T>(mut self, key: &'static str, value: T) -> OperationInfo 
+		where T: ToString
+	{
+		self.details.insert(key, value.to_string());
+		self
+	}
 }
 
 
@@ -0,0 +1,738 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines operations for generating ramps of colors.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use address::{Address, Reference};
+use colors::{self, ArcDirection, ColorSpace, GamutMap};
+use utilities::clamped;
+use data::Data;
+use expression::Expression;
+use operation::{
+	revert_on_error,
+	set_target,
+	HistoryEntry,
+	OperationInfo,
+	PaletteOperation,
+	Undo,
+};
+use result::{Error, Result};
+
+// Non-local imports.
+use color::Color;
+
+// Standard imports.
+use std::cmp;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::rc::Rc;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RampLayout
+////////////////////////////////////////////////////////////////////////////////
+/// Determines where `InsertRamp` places the generated colors.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// let (black, gray) = (Color::new(0, 0, 0), Color::new(100, 100, 100));
+/// for page in 0..3 {
+/// 	pal.apply(Box::new(InsertColor::new(black)
+/// 		.located_at(Address::new(page, 0, 0)))).unwrap();
+/// 	pal.apply(Box::new(InsertColor::new(gray)
+/// 		.located_at(Address::new(page, 0, 9)))).unwrap();
+/// }
+/// for page in 0..2 {
+/// 	pal.apply(Box::new(InsertColor::new(gray)
+/// 		.located_at(Address::new(page, 0, 2)))).unwrap();
+/// }
+///
+/// let ramp = |page, layout| Box::new(InsertRamp::new(
+/// 		Address::new(page, 0, 0), 
+/// 		Address::new(page, 0, 9), 
+/// 		2)
+/// 	.layout(layout));
+///
+/// // Free addresses after the start of the ramp are used.
+/// pal.apply(ramp(0, RampLayout::AfterSources)).unwrap();
+/// assert_eq!(pal.color(Address::new(0, 0, 1)), Some(Color::new(33, 33, 33)));
+/// assert_eq!(pal.color(Address::new(0, 0, 3)), Some(Color::new(67, 67, 67)));
+///
+/// // The colors must directly follow the start of the ramp.
+/// assert!(pal.apply(ramp(1, RampLayout::Contiguous)).is_err());
+/// pal.apply(ramp(2, RampLayout::Contiguous)).unwrap();
+/// assert_eq!(pal.color(Address::new(2, 0, 1)), Some(Color::new(33, 33, 33)));
+/// assert_eq!(pal.color(Address::new(2, 0, 2)), Some(Color::new(67, 67, 67)));
+///
+/// // The colors are placed at the given address.
+/// pal.apply(ramp(0, RampLayout::Separate(Address::new(3, 4, 5)))).unwrap();
+/// assert_eq!(pal.color(Address::new(3, 4, 5)), Some(Color::new(33, 33, 33)));
+/// assert_eq!(pal.color(Address::new(3, 4, 6)), Some(Color::new(67, 67, 67)));
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum RampLayout {
+	/// Places the colors in the free addresses following the start of the 
+	/// ramp, or in the first free addresses of the group the ramp is confined
+	/// to.
+	AfterSources,
+	/// Places the colors in the addresses directly following the start of the 
+	/// ramp, skipping the end of the ramp. Fails if any of the addresses are 
+	/// occupied and the operation is not configured to overwrite them.
+	Contiguous,
+	/// Places the colors in the free addresses starting at the given address.
+	Separate(Address),
+}
+
+
+impl Default for RampLayout {
+	fn default() -> Self {
+		RampLayout::AfterSources
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// InsertRamp
+////////////////////////////////////////////////////////////////////////////////
+/// Inserts a ramp of colors interpolated between the colors of two cells.
+/// 
+/// The generated colors are computed when the operation is applied, so they 
+/// will not follow later changes to the source cells.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+/// pal.apply(Box::new(InsertColor::new(Color::new(100, 100, 100)))).unwrap();
+///
+/// pal.apply(Box::new(InsertRamp::new(
+/// 	Address::new(0, 0, 0), 
+/// 	Address::new(0, 0, 1), 
+/// 	3))).unwrap();
+///
+/// assert_eq!(pal.color(Address::new(0, 0, 2)), Some(Color::new(25, 25, 25)));
+/// assert_eq!(pal.color(Address::new(0, 0, 3)), Some(Color::new(50, 50, 50)));
+/// assert_eq!(pal.color(Address::new(0, 0, 4)), Some(Color::new(75, 75, 75)));
+/// ```
+#[derive(Clone)]
+pub struct InsertRamp {
+	/// The address of the color at the start of the ramp.
+	from: Address,
+	/// The address of the color at the end of the ramp.
+	to: Address,
+	/// The number of colors to generate.
+	count: usize,
+	/// Where to place the colors.
+	layout: RampLayout,
+	/// The group to place the colors in.
+	group: Option<Reference>,
+	/// Whether to place the colors between the source cells.
+	fill_between: bool,
+	/// Whether to overwrite existing cells when generating new ones.
+	overwrite: bool,
+	/// Whether to reduce the count to fit the available space.
+	fit_available: bool,
+	/// Whether to generate the colors from the end of the ramp to the start.
+	reversed: bool,
+	/// The easing curve applied to the interpolation ratio.
+	curve: Option<Rc<Fn(f32) -> f32>>,
+	/// The color space in which to interpolate the colors.
+	space: ColorSpace,
+	/// The direction in which hues move around the color wheel.
+	arc: ArcDirection,
+	/// The minimum and maximum HSL lightness of the generated colors.
+	lightness_band: Option<(f32, f32)>,
+	/// The method used to bring out-of-gamut colors into gamut.
+	gamut_map: GamutMap,
+}
+
+
+impl InsertRamp {
+	/// Creates a new InsertRamp operation generating `count` colors between the
+	/// colors at the given addresses.
+	#[inline]
+	pub fn new(from: Address, to: Address, count: usize) -> InsertRamp {
+		InsertRamp {
+			from: from,
+			to: to,
+			count: count,
+			layout: RampLayout::default(),
+			group: None,
+			fill_between: false,
+			overwrite: false,
+			fit_available: false,
+			reversed: false,
+			curve: None,
+			space: ColorSpace::default(),
+			arc: ArcDirection::default(),
+			lightness_band: None,
+			gamut_map: GamutMap::default(),
+		}
+	}
+
+	/// Creates a new InsertRamp operation which fills the addresses lying 
+	/// strictly between the given addresses, which must be on the same line.
+	///
+	/// # Errors
+	///
+	/// Applying the operation returns an `InvalidAddress` error if the 
+	/// addresses are not on the same line, and an `AddressInUse` error if any 
+	/// of the addresses between them are occupied and the operation is not 
+	/// configured to overwrite them.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(90, 90, 90))
+	/// 	.located_at(Address::new(0, 0, 9)))).unwrap();
+	///
+	/// pal.apply(Box::new(InsertRamp::fill_between(
+	/// 	Address::new(0, 0, 0), 
+	/// 	Address::new(0, 0, 9)))).unwrap();
+	///
+	/// assert_eq!(pal.len(), 10);
+	/// assert_eq!(pal.color(Address::new(0, 0, 1)), Some(Color::new(10, 10, 10)));
+	/// assert_eq!(pal.color(Address::new(0, 0, 8)), Some(Color::new(80, 80, 80)));
+	/// ```
+	pub fn fill_between(from: Address, to: Address) -> InsertRamp {
+		InsertRamp {
+			fill_between: true,
+			.. InsertRamp::new(from, to, 0)
+		}
+	}
+
+	/// Sets the location to start placing the colors. This is equivalent to 
+	/// using the `RampLayout::Separate` layout.
+	pub fn located_at(mut self, location: Address) -> InsertRamp {
+		self.layout = RampLayout::Separate(location);
+		self
+	}
+
+	/// Sets where to place the colors.
+	pub fn layout(mut self, layout: RampLayout) -> InsertRamp {
+		self.layout = layout;
+		self
+	}
+
+	/// Confines the colors to the given group. If no location is set, the 
+	/// colors will be placed starting at the first free address in the group.
+	///
+	/// # Errors
+	///
+	/// Applying the operation returns a `GroupFull` error if the colors do not
+	/// fit in the group, and an `InvalidAddress` error if a `Separate` layout 
+	/// starts outside of the group.
+	pub fn within(mut self, group: Reference) -> InsertRamp {
+		self.group = Some(group);
+		self
+	}
+
+	/// Configures the operation to overwrite existing cells when inserted.
+	pub fn overwrite(mut self, overwrite: bool) -> InsertRamp {
+		self.overwrite = overwrite;
+		self
+	}
+
+	/// Configures the operation to reduce the number of generated colors to fit
+	/// the available space instead of failing. The colors are evenly spaced 
+	/// across the reduced count, which is reported by the `generated` detail of
+	/// the applied operation's history entry. The requested count is kept, so 
+	/// applying the operation again fits the full count anew.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::operation::*;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// dat.maximum_page_count = 1;
+	/// dat.default_line_count = 1;
+	/// dat.default_column_count = 6;
+	/// *dat.create_cell(Address::new(0, 0, 0)).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(0, 0, 0));
+	/// *dat.create_cell(Address::new(0, 0, 1)).unwrap().borrow_mut() 
+	/// 	= Expression::Color(Color::new(100, 100, 100));
+	///
+	/// let mut op = InsertRamp::new(
+	/// 		Address::new(0, 0, 0), 
+	/// 		Address::new(0, 0, 1), 
+	/// 		10)
+	/// 	.fit_available(true);
+	/// let entry = op.apply(&mut dat).unwrap();
+	///
+	/// assert_eq!(dat.len(), 6);
+	/// assert_eq!(entry.info.details["generated"], "4");
+	/// assert_eq!(op.info().details["count"], "10");
+	/// for (column, &v) in [20, 40, 60, 80].iter().enumerate() {
+	/// 	let address = Address::new(0, 0, column as u8 + 2);
+	/// 	assert_eq!(
+	/// 		dat.cell(address).and_then(|cell| cell.color()), 
+	/// 		Some(Color::new(v, v, v)));
+	/// }
+	/// ```
+	pub fn fit_available(mut self, fit_available: bool) -> InsertRamp {
+		self.fit_available = fit_available;
+		self
+	}
+
+	/// Configures the operation to generate the colors in reverse order, so that
+	/// the first color lies nearest the end of the ramp.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(100, 100, 100)))).unwrap();
+	///
+	/// pal.apply(Box::new(InsertRamp::new(
+	/// 		Address::new(0, 0, 0), 
+	/// 		Address::new(0, 0, 1), 
+	/// 		3)
+	/// 	.reversed(true))).unwrap();
+	///
+	/// assert_eq!(pal.color(Address::new(0, 0, 2)), Some(Color::new(75, 75, 75)));
+	/// assert_eq!(pal.color(Address::new(0, 0, 3)), Some(Color::new(50, 50, 50)));
+	/// assert_eq!(pal.color(Address::new(0, 0, 4)), Some(Color::new(25, 25, 25)));
+	/// ```
+	pub fn reversed(mut self, reversed: bool) -> InsertRamp {
+		self.reversed = reversed;
+		self
+	}
+
+	/// Sets an easing curve which maps each color's evenly spaced position 
+	/// along the ramp, between 0 and 1, to the interpolation ratio used to 
+	/// generate it.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(100, 100, 100)))).unwrap();
+	///
+	/// let exponent = 2;
+	/// pal.apply(Box::new(InsertRamp::new(
+	/// 		Address::new(0, 0, 0), 
+	/// 		Address::new(0, 0, 1), 
+	/// 		1)
+	/// 	.curve(move |t| t.powi(exponent)))).unwrap();
+	///
+	/// // The eased midpoint lies a quarter of the way along a linear ramp.
+	/// pal.apply(Box::new(InsertRamp::new(
+	/// 		Address::new(0, 0, 0), 
+	/// 		Address::new(0, 0, 1), 
+	/// 		3))).unwrap();
+	///
+	/// assert_eq!(
+	/// 	pal.color(Address::new(0, 0, 2)), 
+	/// 	pal.color(Address::new(0, 0, 3)));
+	/// ```
+	pub fn curve<F>(mut self, curve: F) -> InsertRamp 
+		where F: Fn(f32) -> f32 + 'static
+	{
+		self.curve = Some(Rc::new(curve));
+		self
+	}
+
+	/// Sets the color space in which the colors are interpolated.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::colors::ColorSpace;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(255, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 255)))).unwrap();
+	///
+	/// let spaces = [ColorSpace::Rgb, ColorSpace::LinearRgb, ColorSpace::Hsl];
+	/// for (i, &space) in spaces.iter().enumerate() {
+	/// 	pal.apply(Box::new(InsertRamp::new(
+	/// 			Address::new(0, 0, 0), 
+	/// 			Address::new(0, 0, 1), 
+	/// 			1)
+	/// 		.located_at(Address::new(1, 0, i as u8))
+	/// 		.in_space(space))).unwrap();
+	/// }
+	///
+	/// let rgb = pal.color(Address::new(1, 0, 0));
+	/// let linear = pal.color(Address::new(1, 0, 1));
+	/// let hsl = pal.color(Address::new(1, 0, 2));
+	/// assert!(rgb != linear && linear != hsl && rgb != hsl);
+	/// ```
+	pub fn in_space(mut self, space: ColorSpace) -> InsertRamp {
+		self.space = space;
+		self
+	}
+
+	/// Sets the direction in which hues move around the color wheel when the 
+	/// colors are interpolated in the `Hsv` or `Hsl` color space. This allows 
+	/// the ramp to take the long way around the wheel.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::colors::{hsv, ArcDirection, ColorSpace};
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(255, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(255, 0, 255)))).unwrap();
+	///
+	/// // Walk from red (0 degrees) to 300 degrees the long way.
+	/// pal.apply(Box::new(InsertRamp::new(
+	/// 		Address::new(0, 0, 0), 
+	/// 		Address::new(0, 0, 1), 
+	/// 		3)
+	/// 	.in_space(ColorSpace::Hsv)
+	/// 	.along_arc(ArcDirection::Clockwise))).unwrap();
+	///
+	/// let quarter = pal.color(Address::new(0, 0, 2)).unwrap();
+	/// let [h, s, v] = hsv(quarter);
+	/// assert!((h - 75.0).abs() < 1.5);
+	/// assert!(s > 0.99 && v > 0.99);
+	/// ```
+	pub fn along_arc(mut self, direction: ArcDirection) -> InsertRamp {
+		self.arc = direction;
+		self
+	}
+
+	/// Clamps the HSL lightness of each generated color between the given 
+	/// ratios.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::colors::hsl;
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Default, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(255, 255, 255)))).unwrap();
+	///
+	/// pal.apply(Box::new(InsertRamp::new(
+	/// 		Address::new(0, 0, 0), 
+	/// 		Address::new(0, 0, 1), 
+	/// 		9)
+	/// 	.clamp_lightness(0.2, 0.8))).unwrap();
+	///
+	/// for column in 2..11 {
+	/// 	let color = pal.color(Address::new(0, 0, column)).unwrap();
+	/// 	let [_, _, l] = hsl(color);
+	/// 	assert!(l >= 0.195 && l <= 0.805);
+	/// }
+	/// ```
+	pub fn clamp_lightness(mut self, min: f32, max: f32) -> InsertRamp {
+		self.lightness_band = Some((min, max));
+		self
+	}
+
+	/// Sets the method used to bring interpolated colors lying outside of the 
+	/// sRGB gamut into gamut.
+	pub fn gamut_map(mut self, gamut_map: GamutMap) -> InsertRamp {
+		self.gamut_map = gamut_map;
+		self
+	}
+
+	/// Returns the target addresses for `count` colors.
+	fn find_targets(&self, data: &mut Data, count: usize) 
+		-> Result<Vec<Address>>
+	{
+		let targets = match self.layout {
+			RampLayout::Separate(address) => {
+				if let Some(ref group) = self.group {
+					if !group.contains(&address) {
+						return Err(Error::InvalidAddress(address));
+					}
+				}
+				data.find_targets(
+					count,
+					address,
+					self.overwrite,
+					Some(vec![self.from, self.to])
+				)?
+			},
+
+			RampLayout::Contiguous => {
+				let start = self.from.wrapping_step(
+					1,
+					data.maximum_page_count,
+					data.line_count(&Reference::page_of(&self.from)),
+					data.column_count(&Reference::line_of(&self.from)),
+				);
+				let targets = data.find_targets(
+					count,
+					start,
+					true,
+					Some(vec![self.from, self.to])
+				)?;
+				if !self.overwrite {
+					for &target in &targets {
+						if data.cell(target).is_some() {
+							return Err(Error::AddressInUse(target));
+						}
+					}
+				}
+				targets
+			},
+
+			RampLayout::AfterSources => {
+				let starting_address = if let Some(ref group) = self.group {
+					data.first_free_address_in(group)
+						.ok_or_else(|| Error::GroupFull(group.clone()))?
+				} else {
+					data.first_free_address_after(self.from)?
+				};
+				data.find_targets(
+					count,
+					starting_address,
+					self.overwrite,
+					Some(vec![self.from, self.to])
+				)?
+			},
+		};
+		if let Some(ref group) = self.group {
+			if targets.iter().any(|target| !group.contains(target)) {
+				return Err(Error::GroupFull(group.clone()));
+			}
+		}
+		Ok(targets)
+	}
+
+	/// Returns the number of free addresses available to the ramp, up to the 
+	/// requested count.
+	fn free_count(&self, data: &mut Data) -> Result<usize> {
+		let start = match self.layout {
+			RampLayout::Contiguous => return Ok(self.count),
+			RampLayout::Separate(address) => address,
+			RampLayout::AfterSources => match self.group {
+				Some(ref group) => match data.first_free_address_in(group) {
+					Some(address) => address,
+					None => return Ok(0),
+				},
+				None => data.first_free_address_after(self.from)?,
+			},
+		};
+
+		let mut free = BTreeSet::new();
+		let mut next = start;
+		while free.len() < self.count {
+			next = match data.first_free_address_after(next) {
+				Ok(address) => address,
+				Err(Error::MaxCellLimitExceeded) => break,
+				Err(e) => return Err(e),
+			};
+			if let Some(ref group) = self.group {
+				if !group.contains(&next) { break; }
+			}
+			if next != self.from && next != self.to && !free.insert(next) {
+				// We've looped all the way around.
+				break;
+			}
+			next = next.wrapping_step(
+				1,
+				data.maximum_page_count,
+				data.line_count(&Reference::page_of(&next)),
+				data.column_count(&Reference::line_of(&next)),
+			);
+		}
+		Ok(free.len())
+	}
+
+	/// Returns the addresses between the source cells.
+	fn addresses_between(&self, data: &Data) -> Result<Vec<Address>> {
+		let (from, to) = if self.from < self.to {
+			(self.from, self.to)
+		} else {
+			(self.to, self.from)
+		};
+		if from.page != to.page || from.line != to.line {
+			return Err(Error::InvalidAddress(self.to));
+		}
+
+		let mut targets = Vec::new();
+		for column in (from.column + 1)..to.column {
+			let address = Address::new(from.page, from.line, column);
+			if !self.overwrite && data.cell(address).is_some() {
+				return Err(Error::AddressInUse(address));
+			}
+			targets.push(address);
+		}
+		if self.from > self.to {
+			targets.reverse();
+		}
+		Ok(targets)
+	}
+
+	/// Returns the color located at the ratio `t` between the given colors.
+	fn interpolate(&self, start: Color, end: Color, t: f32) -> Color {
+		let color = self.space.interpolate_with(
+			start, 
+			end, 
+			t, 
+			self.arc, 
+			self.gamut_map);
+		match self.lightness_band {
+			Some((min, max)) => {
+				let [h, s, l] = colors::hsl(color);
+				colors::from_hsl_mapped(
+					[h, s, clamped(l, min, max)], 
+					self.gamut_map)
+			},
+			None => color,
+		}
+	}
+}
+
+
+impl fmt::Debug for InsertRamp {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "InsertRamp {{ \
+			from: {:?}, \
+			to: {:?}, \
+			count: {}, \
+			layout: {:?}, \
+			group: {:?}, \
+			fill_between: {}, \
+			overwrite: {}, \
+			fit_available: {}, \
+			reversed: {}, \
+			curve: {}, \
+			space: {:?}, \
+			arc: {:?}, \
+			lightness_band: {:?}, \
+			gamut_map: {:?} }}",
+			self.from,
+			self.to,
+			self.count,
+			self.layout,
+			self.group,
+			self.fill_between,
+			self.overwrite,
+			self.fit_available,
+			self.reversed,
+			if self.curve.is_some() { "<function>" } else { "None" },
+			self.space,
+			self.arc,
+			self.lightness_band,
+			self.gamut_map,
+		)
+	}
+}
+
+
+impl PaletteOperation for InsertRamp {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Insert Ramp")
+			.detail("from", self.from)
+			.detail("to", self.to)
+			.detail("count", self.count)
+			.detail("layout", format!("{:?}", self.layout))
+			.detail("reversed", self.reversed)
+			.detail("space", format!("{:?}", self.space))
+			.detail("arc", format!("{:?}", self.arc))
+			.detail("gamut_map", format!("{:?}", self.gamut_map))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		// Get source colors.
+		let start = data.cell(self.from)
+			.and_then(|cell| cell.color())
+			.ok_or(Error::EmptyAddress(self.from))?;
+		let end = data.cell(self.to)
+			.and_then(|cell| cell.color())
+			.ok_or(Error::EmptyAddress(self.to))?;
+
+		// Get targets.
+		let targets = if self.fill_between {
+			self.addresses_between(data)?
+		} else if self.fit_available {
+			// Reduce the count to the free space, and then further if the 
+			// layout still does not fit.
+			let mut count = cmp::max(1, cmp::min(
+				self.count,
+				self.free_count(data)?));
+			loop {
+				match self.find_targets(data, count) {
+					Ok(targets) => break targets,
+					Err(Error::MaxCellLimitExceeded) if count > 1 => count -= 1,
+					Err(e) => return Err(e),
+				}
+			}
+		} else {
+			let count = self.count;
+			self.find_targets(data, count)?
+		};
+
+		// Set targets.
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (i, &target) in targets.iter().enumerate() {
+				let mut t = (i + 1) as f32 / (targets.len() + 1) as f32;
+				if self.reversed { t = 1.0 - t; }
+				if let Some(ref curve) = self.curve { t = curve(t); }
+				let color = self.interpolate(start, end, t);
+				set_target(data, target, Expression::Color(color), undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info().detail("generated", targets.len()),
+			undo: Box::new(undo),
+		})
+	}
+}
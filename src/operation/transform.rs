@@ -0,0 +1,534 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Defines operations which transform the colors in a selection.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use address::Selection;
+use colors::{self, ColorMetric, GamutMap};
+use data::Data;
+use expression::Expression;
+use operation::{
+	revert_on_error,
+	set_target,
+	unlocked_colors,
+	HistoryEntry,
+	OperationInfo,
+	PaletteOperation,
+	Undo,
+};
+use result::Result;
+use utilities::clamped;
+
+// Non-local imports.
+use color::Color;
+
+// Standard imports.
+use std::fmt;
+
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Recolor
+////////////////////////////////////////////////////////////////////////////////
+/// Sets the hue and saturation of each color in a selection, preserving their
+/// lightness.
+/// 
+/// # Example
+///
+/// ```rust
+/// # extern crate interval;
+/// # extern crate palette;
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::address::Selection;
+/// use palette::colors::hsl;
+/// use interval::Interval;
+/// 
+/// # fn main() {
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// for &v in [50, 128, 200].iter() {
+/// 	pal.apply(Box::new(InsertColor::new(Color::new(v, v, v)))).unwrap();
+/// }
+///
+/// let line = Selection::new(vec![
+/// 	Interval::closed(Address::new(0, 0, 0), Address::new(0, 0, 2))
+/// ]);
+/// pal.apply(Box::new(Recolor::new(line, 240.0, 1.0))).unwrap();
+///
+/// for (column, &v) in [50, 128, 200].iter().enumerate() {
+/// 	let [h, _, l] = hsl(pal.color(Address::new(0, 0, column as u8)).unwrap());
+/// 	assert!((h - 240.0).abs() < 1.0);
+/// 	assert!((l - v as f32 / 255.0).abs() < 0.01);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recolor {
+	/// The cells to recolor.
+	selection: Selection,
+	/// The hue to assign, in degrees.
+	hue: f32,
+	/// The saturation to assign. Saturations above 1 are brought into gamut 
+	/// using the operation's `GamutMap`.
+	saturation: f32,
+	/// The method used to bring out-of-gamut results into gamut.
+	gamut_map: GamutMap,
+}
+
+
+impl Recolor {
+	/// Creates a new Recolor operation.
+	#[inline]
+	pub fn new(selection: Selection, hue: f32, saturation: f32) -> Recolor {
+		Recolor {
+			selection: selection,
+			hue: hue,
+			saturation: saturation,
+			gamut_map: GamutMap::default(),
+		}
+	}
+
+	/// Sets the method used to bring out-of-gamut results into gamut.
+	pub fn gamut_map(mut self, gamut_map: GamutMap) -> Recolor {
+		self.gamut_map = gamut_map;
+		self
+	}
+}
+
+
+impl PaletteOperation for Recolor {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Recolor")
+			.detail("hue", self.hue)
+			.detail("saturation", self.saturation)
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (address, color) in unlocked_colors(data, &self.selection) {
+				let [_, _, l] = colors::hsl(color);
+				let recolored = colors::from_hsl_mapped(
+					[self.hue, self.saturation, l], 
+					self.gamut_map);
+				set_target(data, address, Expression::Color(recolored), undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// HueMode
+////////////////////////////////////////////////////////////////////////////////
+/// Determines how hues are kept in range when they are adjusted.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HueMode {
+	/// Wraps hues around the color wheel at 360 degrees.
+	Wrap,
+	/// Clamps hues to the band between the given minimum and maximum, in 
+	/// degrees.
+	Clamp(f32, f32),
+}
+
+
+impl HueMode {
+	/// Returns the hue produced by rotating the given hue by `degrees`.
+	fn rotate(self, hue: f32, degrees: f32) -> f32 {
+		match self {
+			HueMode::Wrap => ((hue + degrees) % 360.0 + 360.0) % 360.0,
+			HueMode::Clamp(min, max) => clamped(hue + degrees, min, max),
+		}
+	}
+}
+
+
+impl Default for HueMode {
+	fn default() -> Self {
+		HueMode::Wrap
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RotateHue
+////////////////////////////////////////////////////////////////////////////////
+/// Rotates the hue of each color in a selection by the given number of 
+/// degrees, preserving their saturation and lightness.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::colors::{from_hsl, hsl};
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(from_hsl([50.0, 1.0, 0.5])))).unwrap();
+/// pal.apply(Box::new(InsertColor::new(from_hsl([350.0, 1.0, 0.5])))).unwrap();
+///
+/// // Clamped rotation stops at the edge of the band.
+/// pal.apply(Box::new(RotateHue::new(Address::new(0, 0, 0).into(), 30.0)
+/// 	.mode(HueMode::Clamp(0.0, 60.0)))).unwrap();
+/// let [h, _, _] = hsl(pal.color(Address::new(0, 0, 0)).unwrap());
+/// assert!((h - 60.0).abs() < 1.0);
+///
+/// // Wrapping rotation continues around the color wheel.
+/// pal.apply(Box::new(RotateHue::new(Address::new(0, 0, 1).into(), 30.0)))
+/// 	.unwrap();
+/// let [h, _, _] = hsl(pal.color(Address::new(0, 0, 1)).unwrap());
+/// assert!((h - 20.0).abs() < 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RotateHue {
+	/// The cells to modify.
+	selection: Selection,
+	/// The angle to rotate the hues by, in degrees.
+	degrees: f32,
+	/// How the rotated hues are kept in range.
+	mode: HueMode,
+}
+
+
+impl RotateHue {
+	/// Creates a new RotateHue operation.
+	#[inline]
+	pub fn new(selection: Selection, degrees: f32) -> RotateHue {
+		RotateHue {
+			selection: selection,
+			degrees: degrees,
+			mode: HueMode::default(),
+		}
+	}
+
+	/// Sets how the rotated hues are kept in range.
+	pub fn mode(mut self, mode: HueMode) -> RotateHue {
+		self.mode = mode;
+		self
+	}
+}
+
+
+impl PaletteOperation for RotateHue {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Rotate Hue")
+			.detail("degrees", self.degrees)
+			.detail("mode", format!("{:?}", self.mode))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (address, color) in unlocked_colors(data, &self.selection) {
+				let [h, s, l] = colors::hsl(color);
+				let hue = self.mode.rotate(h, self.degrees);
+				let rotated = colors::from_hsl([hue, s, l]);
+				set_target(data, address, Expression::Color(rotated), undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SnapToPalette
+////////////////////////////////////////////////////////////////////////////////
+/// Replaces every color in the palette with the nearest color in a reference 
+/// palette. Locked cells are left unchanged.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::colors::ColorMetric;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// for i in 0..10 {
+/// 	let v = i * 28;
+/// 	pal.apply(Box::new(InsertColor::new(Color::new(v, v, v)))).unwrap();
+/// }
+///
+/// let reference = vec![
+/// 	Color::new(0, 0, 0),
+/// 	Color::new(128, 128, 128),
+/// 	Color::new(255, 255, 255),
+/// ];
+/// pal.apply(Box::new(SnapToPalette::new(
+/// 	reference.clone(), 
+/// 	ColorMetric::Euclidean))).unwrap();
+///
+/// for column in 0..10 {
+/// 	let color = pal.color(Address::new(0, 0, column)).unwrap();
+/// 	assert!(reference.contains(&color));
+/// }
+/// assert_eq!(pal.color(Address::new(0, 0, 4)), Some(Color::new(128, 128, 128)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapToPalette {
+	/// The colors to snap to.
+	reference: Vec<Color>,
+	/// The metric used to find the nearest reference color.
+	metric: ColorMetric,
+}
+
+
+impl SnapToPalette {
+	/// Creates a new SnapToPalette operation.
+	#[inline]
+	pub fn new(reference: Vec<Color>, metric: ColorMetric) -> SnapToPalette {
+		SnapToPalette {
+			reference: reference,
+			metric: metric,
+		}
+	}
+}
+
+
+impl PaletteOperation for SnapToPalette {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Snap To Palette")
+			.detail("reference size", self.reference.len())
+			.detail("metric", format!("{:?}", self.metric))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			let everything = Selection::everything();
+			for (address, color) in unlocked_colors(data, &everything) {
+				if let Some(i) = self.metric.nearest(color, &self.reference) {
+					let snapped = Expression::Color(self.reference[i]);
+					set_target(data, address, snapped, undo)?;
+				}
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ApplyMatrix
+////////////////////////////////////////////////////////////////////////////////
+/// Transforms each color in a selection by multiplying its RGB ratios by a 
+/// matrix and adding an offset.
+/// 
+/// # Example
+///
+/// ```rust
+/// # extern crate interval;
+/// # extern crate palette;
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::address::Selection;
+/// use interval::Interval;
+/// 
+/// # fn main() {
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(255, 255, 255)))).unwrap();
+///
+/// let sepia = [
+/// 	[0.393, 0.769, 0.189],
+/// 	[0.349, 0.686, 0.168],
+/// 	[0.272, 0.534, 0.131],
+/// ];
+/// let selection = Selection::new(vec![
+/// 	Interval::closed(Address::new(0, 0, 0), Address::new(0, 0, 0))
+/// ]);
+/// pal.apply(Box::new(ApplyMatrix::new(selection, sepia, [0.0; 3]))).unwrap();
+///
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), Some(Color::new(255, 255, 239)));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ApplyMatrix {
+	/// The cells to transform.
+	selection: Selection,
+	/// The matrix to multiply the colors by.
+	matrix: [[f32; 3]; 3],
+	/// The offset to add to the colors.
+	offset: [f32; 3],
+	/// The method used to bring out-of-gamut results into gamut.
+	gamut_map: GamutMap,
+}
+
+
+impl ApplyMatrix {
+	/// Creates a new ApplyMatrix operation.
+	#[inline]
+	pub fn new(selection: Selection, matrix: [[f32; 3]; 3], offset: [f32; 3]) 
+		-> ApplyMatrix 
+	{
+		ApplyMatrix {
+			selection: selection,
+			matrix: matrix,
+			offset: offset,
+			gamut_map: GamutMap::default(),
+		}
+	}
+
+	/// Sets the method used to bring out-of-gamut results into gamut.
+	pub fn gamut_map(mut self, gamut_map: GamutMap) -> ApplyMatrix {
+		self.gamut_map = gamut_map;
+		self
+	}
+
+	/// Returns the given color transformed by the matrix.
+	fn transform(&self, color: Color) -> Color {
+		let c = colors::ratios(color);
+		let mut result = self.offset;
+		for (r, row) in result.iter_mut().zip(self.matrix.iter()) {
+			*r += row[0] * c[0] + row[1] * c[1] + row[2] * c[2];
+		}
+		colors::from_ratios_mapped(result, self.gamut_map)
+	}
+}
+
+
+impl PaletteOperation for ApplyMatrix {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Apply Matrix")
+			.detail("matrix", format!("{:?}", self.matrix))
+			.detail("offset", format!("{:?}", self.offset))
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (address, color) in unlocked_colors(data, &self.selection) {
+				let transformed = self.transform(color);
+				set_target(data, address, Expression::Color(transformed), undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// MapColors
+////////////////////////////////////////////////////////////////////////////////
+/// Replaces each color in a selection with the result of a function applied to
+/// it.
+/// 
+/// # Example
+///
+/// ```rust
+/// use palette::*;
+/// use palette::operation::*;
+/// use palette::address::Selection;
+/// use palette::colors::scale;
+/// 
+/// let mut pal = Palette::new("Example", Format::Default, true);
+/// pal.apply(Box::new(InsertColor::new(Color::new(50, 100, 200)))).unwrap();
+///
+/// pal.apply(Box::new(MapColors::new(
+/// 	Selection::everything(), 
+/// 	Box::new(|color| scale(color, 2.0))))).unwrap();
+///
+/// assert_eq!(pal.color(Address::new(0, 0, 0)), Some(Color::new(100, 200, 255)));
+/// ```
+pub struct MapColors {
+	/// The cells to transform.
+	selection: Selection,
+	/// The function to apply to each color.
+	function: Box<Fn(Color) -> Color>,
+}
+
+
+impl MapColors {
+	/// Creates a new MapColors operation.
+	#[inline]
+	pub fn new(selection: Selection, function: Box<Fn(Color) -> Color>) 
+		-> MapColors 
+	{
+		MapColors {
+			selection: selection,
+			function: function,
+		}
+	}
+}
+
+
+impl fmt::Debug for MapColors {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "MapColors {{ selection: {:?}, function: <function> }}", 
+			self.selection)
+	}
+}
+
+
+impl PaletteOperation for MapColors {
+	fn info(&self) -> OperationInfo {
+		OperationInfo::new("Map Colors")
+	}
+
+	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
+		let mut undo = Undo::new_for(self);
+		revert_on_error(data, &mut undo, |data, undo| {
+			for (address, color) in unlocked_colors(data, &self.selection) {
+				let mapped = (self.function)(color);
+				set_target(data, address, Expression::Color(mapped), undo)?;
+			}
+			Ok(())
+		})?;
+
+		Ok(HistoryEntry {
+			info: self.info(),
+			undo: Box::new(undo),
+		})
+	}
+}
@@ -27,7 +27,7 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 // Local imports.
-use address::Address;
+use address::{Address, Selection};
 use data::Data;
 use expression::Expression;
 use operation::{
@@ -60,6 +60,9 @@ pub struct Undo {
 
 	/// The `Expression`s to restore when applying the Undo.
 	saved: HashMap<Address, Option<Expression>>,
+
+	/// The selection to which restored `Expression`s are restricted.
+	restriction: Option<Selection>,
 }
 
 
@@ -68,11 +71,9 @@ impl Undo {
 	#[inline]
 	fn new() -> Undo {
 		Undo {
-			undoing: OperationInfo {
-				name: "Undo",
-				details: None,
-			},
+			undoing: OperationInfo::new("Undo"),
 			saved: Default::default(),
+			restriction: None,
 		}
 	}
 
@@ -84,6 +85,7 @@ impl Undo {
 		Undo {
 			undoing: operation.info(),
 			saved: Default::default(),
+			restriction: None,
 		}
 	}
 
@@ -95,15 +97,106 @@ impl Undo {
 		}
 	}
 
+	/// Restricts the Undo operation to the given selection. When applied, only
+	/// the changes within the selection are reverted. The remaining changes 
+	/// are kept, so the Undo may be applied again to revert them later.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::operation::*;
+	/// use palette::address::Selection;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let (a, b) = (Address::new(0, 0, 0), Address::new(1, 0, 0));
+	/// let color = |v| Expression::Color(Color::new(v, v, v));
+	/// *dat.create_cell(a).unwrap().borrow_mut() = color(10);
+	/// *dat.create_cell(b).unwrap().borrow_mut() = color(20);
+	///
+	/// // Edit both pages, recording the changes.
+	/// let mut undo = Undo::new_for(&SetColor::new(a, Color::new(0, 0, 0)));
+	/// undo.record(a, Some(color(10)));
+	/// undo.record(b, Some(color(20)));
+	/// *dat.cell(a).unwrap().borrow_mut() = color(30);
+	/// *dat.cell(b).unwrap().borrow_mut() = color(40);
+	///
+	/// let mut undo = undo.restricted_to(Selection::page(0));
+	/// undo.apply(&mut dat).unwrap();
+	///
+	/// assert_eq!(dat.cell(a).unwrap().color(), Some(Color::new(10, 10, 10)));
+	/// assert_eq!(dat.cell(b).unwrap().color(), Some(Color::new(40, 40, 40)));
+	/// assert_eq!(undo.len(), 1);
+	/// ```
+	///
+	/// To revert part of the most recent operation applied to a `Palette`, use
+	/// `Palette::undo_within`.
+	pub fn restricted_to(mut self, selection: Selection) -> Undo {
+		self.restriction = Some(selection);
+		self
+	}
+
+	/// Returns the number of cell changes recorded by the Undo operation.
+	pub fn len(&self) -> usize {
+		self.saved.len()
+	}
+
+	/// Returns whether the Undo operation has recorded no changes.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::data::Data;
+	/// use palette::operation::*;
+	/// 
+	/// let mut dat: Data = Default::default();
+	/// let address = Address::new(0, 0, 0);
+	/// let color = Color::new(12, 50, 78);
+	/// *dat.create_cell(address).unwrap().borrow_mut() = Expression::Color(color);
+	///
+	/// let entry = SetColor::new(address, color).apply(&mut dat).unwrap();
+	/// assert_eq!(entry.undo.info().details["count"], "0");
+	///
+	/// let mut undo = Undo::new_for(&SetColor::new(address, color));
+	/// assert!(undo.is_empty());
+	/// undo.record(address, Some(Expression::Empty));
+	/// assert!(!undo.is_empty());
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.saved.is_empty()
+	}
+
 }
 
 
 impl PaletteOperation for Undo {
 	fn info(&self) -> OperationInfo {
-		OperationInfo {
-			name: "Undo",
-			details: Some(format!("{:?}", self))
-		}
+		OperationInfo::new("Undo")
+			.detail("undoing", self.undoing.name)
+			.detail("count", self.saved.len())
+	}
+
+	fn affected(&self) -> Vec<Address> {
+		let mut affected: Vec<Address> = self.saved
+			.keys()
+			.filter(|address| self.restriction
+				.as_ref()
+				.map_or(true, |selection| selection.contains(address)))
+			.cloned()
+			.collect();
+		affected.sort();
+		affected
+	}
+
+	fn restrict(&mut self, selection: Option<&Selection>) -> bool {
+		self.restriction = selection.cloned();
+		true
+	}
+
+	fn has_pending(&self) -> bool {
+		!self.saved.is_empty()
 	}
 
 	fn apply(&mut self, data: &mut Data) -> Result<HistoryEntry> {
@@ -112,21 +205,21 @@ impl PaletteOperation for Undo {
 		let saved = mem::replace(&mut self.saved, HashMap::new());
 
 		for (address, item) in saved {
+			// Keep changes outside of the restriction pending.
+			if let Some(ref selection) = self.restriction {
+				if !selection.contains(&address) {
+					self.saved.insert(address, item);
+					continue;
+				}
+			}
+
 			match (item.is_some(), data.cell(address).is_some()) {
 
-				(true, true) => { // The cell was modified.
+				(true, exists) => { // The cell was modified or deleted.
 					let elem = item.unwrap();
-					let cell = data.cell(address).unwrap();
+					let cell = data.get_or_create_cell(address)?;
 					let cur = mem::replace(&mut *cell.borrow_mut(), elem);
-					redo.record(address, Some(cur));
-					continue;
-				},
-
-				(true, false) => { // The cell was deleted.
-					let elem = item.unwrap();
-					let cell = data.create_cell(address).unwrap();
-					mem::replace(&mut *cell.borrow_mut(), elem);
-					redo.record(address, None);
+					redo.record(address, if exists {Some(cur)} else {None});
 					continue;
 				},
 
@@ -34,6 +34,7 @@ use address::{
 
 // Standard imports.
 use std::fmt;
+use std::io;
 use std::result;
 use std::error;
 
@@ -71,18 +72,195 @@ pub enum Error {
 	
 	/// An element could not be created because the address was occupied.
 	AddressInUse(Address),
+
+	/// A color could not be inserted because it lies too close to the color at
+	/// the given address.
+	ColorTooClose(Address),
+
+	/// A color could not be inserted because the group has no free addresses.
+	GroupFull(Reference),
+
+	/// Two selections were expected to contain the same number of colors, but
+	/// contained the given numbers of colors instead.
+	SelectionSizeMismatch(usize, usize),
+
+	/// The cell at the given address is locked against modification.
+	SlotLocked(Address),
+
+	/// A color could not be stored because the palette already contains the 
+	/// given maximum number of distinct colors.
+	BudgetExceeded(usize),
+
+	/// A palette could not be converted to another format because it contains
+	/// the first given number of colors, but the format only has room for the 
+	/// second.
+	FormatCapacityExceeded(usize, usize),
+
+	/// The most recent operation could not be partially undone.
+	CannotRestrictUndo,
+
+	/// The cell at the given address could not be modified because it is 
+	/// already borrowed.
+	CellBorrowed(Address),
+
+	/// An I/O error occurred while reading or writing a palette.
+	Io(io::Error),
+}
+
+
+impl Error {
+	/// Returns the `ErrorCategory` of the error.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use palette::*;
+	/// use palette::operation::*;
+	/// use palette::result::{Error, ErrorCategory};
+	/// 
+	/// let mut pal = Palette::new("Example", Format::Zpl, true);
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	/// pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0)))).unwrap();
+	///
+	/// // Filling between occupied cells.
+	/// match pal.apply(Box::new(InsertRamp::fill_between(
+	/// 	Address::new(0, 0, 0), 
+	/// 	Address::new(0, 0, 2))))
+	/// {
+	/// 	Err(e @ Error::AddressInUse(_)) 
+	/// 		=> assert_eq!(e.category(), ErrorCategory::Occupied),
+	/// 	_ => panic!("expected AddressInUse error"),
+	/// }
+	///
+	/// // Deleting an empty cell.
+	/// match pal.apply(Box::new(DeleteCell::new(Address::new(0, 1, 0)))) {
+	/// 	Err(e @ Error::EmptyAddress(_)) 
+	/// 		=> assert_eq!(e.category(), ErrorCategory::Empty),
+	/// 	_ => panic!("expected EmptyAddress error"),
+	/// }
+	///
+	/// // Inserting past the last page.
+	/// match pal.apply(Box::new(InsertColor::new(Color::new(0, 0, 0))
+	/// 	.located_at(Address::new(600, 0, 0)))) 
+	/// {
+	/// 	Err(e @ Error::InvalidAddress(_)) 
+	/// 		=> assert_eq!(e.category(), ErrorCategory::OutOfRange),
+	/// 	_ => panic!("expected InvalidAddress error"),
+	/// }
+	/// ```
+	pub fn category(&self) -> ErrorCategory {
+		match *self {
+			Error::AddressInUse(..)
+				=> ErrorCategory::Occupied,
+
+			Error::EmptyAddress(..)
+				=> ErrorCategory::Empty,
+
+			Error::MaxCellLimitExceeded |
+			Error::GroupFull(..) |
+			Error::BudgetExceeded(..) |
+			Error::FormatCapacityExceeded(..)
+				=> ErrorCategory::Full,
+
+			Error::InvalidAddress(..) |
+			Error::InvalidReference(..) |
+			Error::InvalidReferenceComponent |
+			Error::UnresolvedReferenceComponent
+				=> ErrorCategory::OutOfRange,
+
+			Error::CannotSetDerivedColor |
+			Error::ColorTooClose(..) |
+			Error::SelectionSizeMismatch(..) |
+			Error::SlotLocked(..) |
+			Error::CannotRestrictUndo |
+			Error::CellBorrowed(..)
+				=> ErrorCategory::Constraint,
+
+			Error::Io(..)
+				=> ErrorCategory::Io,
+		}
+	}
 }
 
 
 impl fmt::Display for Error {
 	fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
 		match *self {
+			Error::InvalidAddress(address)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					address
+				),
+
+			Error::InvalidReference(ref reference)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					reference
+				),
+
 			Error::EmptyAddress(address)
 				=> write!(f, "{}: {}", 
 					error::Error::description(self), 
 					address
 				),
 
+			Error::AddressInUse(address)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					address
+				),
+
+			Error::ColorTooClose(address)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					address
+				),
+
+			Error::GroupFull(ref group)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					group
+				),
+
+			Error::SelectionSizeMismatch(a, b)
+				=> write!(f, "{}: {} and {}", 
+					error::Error::description(self), 
+					a,
+					b
+				),
+
+			Error::SlotLocked(address)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					address
+				),
+
+			Error::BudgetExceeded(budget)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					budget
+				),
+
+			Error::CellBorrowed(address)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					address
+				),
+
+			Error::FormatCapacityExceeded(count, capacity)
+				=> write!(f, "{}: {} colors, room for {}", 
+					error::Error::description(self), 
+					count,
+					capacity
+				),
+
+			Error::Io(ref err)
+				=> write!(f, "{}: {}", 
+					error::Error::description(self), 
+					err
+				),
+
 			_	=> write!(f, "{}", error::Error::description(self))
 		}
 	}
@@ -116,6 +294,69 @@ impl error::Error for Error {
 
 			Error::AddressInUse(..)
 				=> "the address is in use",
+
+			Error::ColorTooClose(..)
+				=> "color lies too close to an existing color",
+
+			Error::GroupFull(..)
+				=> "no free addresses in group",
+
+			Error::SelectionSizeMismatch(..)
+				=> "selections contain different numbers of colors",
+
+			Error::SlotLocked(..)
+				=> "the address is locked",
+
+			Error::BudgetExceeded(..)
+				=> "the palette color budget is exhausted",
+
+			Error::FormatCapacityExceeded(..)
+				=> "the colors do not fit in the target format",
+
+			Error::CannotRestrictUndo
+				=> "the most recent operation cannot be partially undone",
+
+			Error::CellBorrowed(..)
+				=> "the cell is already borrowed",
+
+			Error::Io(..)
+				=> "an I/O error occurred",
 		}
 	}
+
+	fn cause(&self) -> Option<&error::Error> {
+		match *self {
+			Error::Io(ref err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+
+impl From<io::Error> for Error {
+	fn from(err: io::Error) -> Error {
+		Error::Io(err)
+	}
+}
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// ErrorCategory
+////////////////////////////////////////////////////////////////////////////////
+/// The broad kinds of failure which an `Error` may represent.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ErrorCategory {
+	/// An operation required an empty address, but it was occupied.
+	Occupied,
+	/// An operation required a color, but the address was empty.
+	Empty,
+	/// The palette or group has no room for more cells.
+	Full,
+	/// An address or reference lies outside of the palette.
+	OutOfRange,
+	/// An operation was rejected by a palette or operation constraint.
+	Constraint,
+	/// Reading or writing a palette failed.
+	Io,
 }
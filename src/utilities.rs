@@ -78,9 +78,60 @@ pub fn clamped(value: f32, lower_bound: f32, upper_bound: f32) -> f32 {
 }
 
 
+/// The method used to convert float values to integers.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum RoundingMode {
+	/// Rounds to the nearest integer, with ties rounded to the even integer.
+	NearestEven,
+	/// Rounds to the nearest integer, with ties rounded away from zero.
+	Round,
+	/// Rounds toward zero.
+	Truncate,
+}
+
+impl RoundingMode {
+	/// Returns the given value rounded to an integer and clamped between 0 and
+	/// 255.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use palette::utilities::RoundingMode;
+	/// assert_eq!(RoundingMode::Round.quantize(127.5), 128);
+	/// assert_eq!(RoundingMode::Truncate.quantize(127.5), 127);
+	/// assert_eq!(RoundingMode::NearestEven.quantize(127.5), 128);
+	/// assert_eq!(RoundingMode::NearestEven.quantize(126.5), 126);
+	/// ```
+	#[cfg_attr(feature = "cargo-clippy", allow(float_cmp))]
+	#[inline]
+	pub fn quantize(self, value: f32) -> u8 {
+		let rounded = match self {
+			RoundingMode::NearestEven => {
+				let floor = value.floor();
+				let diff = value - floor;
+				if diff > 0.5 || (diff == 0.5 && floor % 2.0 != 0.0) {
+					floor + 1.0
+				} else {
+					floor
+				}
+			},
+			RoundingMode::Round => value.round(),
+			RoundingMode::Truncate => value.trunc(),
+		};
+		clamped(rounded, 0.0, 255.0) as u8
+	}
+}
+
+impl Default for RoundingMode {
+	fn default() -> Self {
+		RoundingMode::Round
+	}
+}
+
+
 /// Performs a linear interpolation between `start` and `end`, returning the 
 /// value located at the ratio given by `amount`, which is clamped between 0 and
-/// 1. 
+/// 1. The result is rounded using the default `RoundingMode`.
 ///
 /// # Examples
 ///
@@ -108,7 +159,7 @@ pub fn lerp_u8(start: u8, end:u8, amount: f32) -> u8 {
 
 	let s = if start > end {end} else {start};
 	let e = if start > end {start} else {end};
-	(((e-s) as f32) * a) as u8 + s
+	RoundingMode::default().quantize(((e-s) as f32) * a) + s
 }
 
 
@@ -0,0 +1,117 @@
+// The MIT License (MIT)
+// 
+// Copyright (c) 2016 Skylor R. Schermer
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in 
+// all copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides `PaletteView`, an immutable snapshot of a palette's resolved 
+//! colors which may be shared between threads.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+// Local imports.
+use address::Address;
+
+// Non-local imports.
+use color::Color;
+
+// Standard imports.
+use std::collections::BTreeMap;
+
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PaletteView
+////////////////////////////////////////////////////////////////////////////////
+/// An immutable snapshot of the resolved colors of a palette's `Cell`s.
+///
+/// Unlike the `Data` it was created from, a `PaletteView` holds no `RefCell`s
+/// or `Rc`s, so it is `Send + Sync` and may be queried from multiple threads.
+///
+/// # Example
+///
+/// ```rust
+/// use palette::data::Data;
+/// use palette::{Address, Color, Expression};
+/// use std::sync::Arc;
+/// use std::thread;
+/// 
+/// let mut dat: Data = Default::default();
+/// *dat.create_cell(Address::new(0, 0, 0)).unwrap().borrow_mut() 
+/// 	= Expression::Color(Color::new(12, 50, 78));
+///
+/// let view = Arc::new(dat.to_view());
+/// let handles: Vec<_> = (0..4).map(|_| {
+/// 	let view = view.clone();
+/// 	thread::spawn(move || view.color(Address::new(0, 0, 0)))
+/// }).collect();
+///
+/// for handle in handles {
+/// 	assert_eq!(handle.join().unwrap(), Some(Color::new(12, 50, 78)));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PaletteView {
+	/// The resolved colors of the snapshotted cells.
+	colors: BTreeMap<Address, Option<Color>>,
+}
+
+
+impl PaletteView {
+	/// Creates a new `PaletteView` from a collection of resolved colors.
+	pub fn new<I>(colors: I) -> Self 
+		where I: IntoIterator<Item=(Address, Option<Color>)>
+	{
+		PaletteView {
+			colors: colors.into_iter().collect(),
+		}
+	}
+
+	/// Returns the number of cells in the `PaletteView`.
+	pub fn len(&self) -> usize {
+		self.colors.len()
+	}
+
+	/// Returns whether the `PaletteView` contains any cells.
+	pub fn is_empty(&self) -> bool {
+		self.colors.is_empty()
+	}
+
+	/// Returns the color at the given address, or None if the cell is empty or
+	/// did not resolve to a color.
+	pub fn color(&self, address: Address) -> Option<Color> {
+		self.colors.get(&address).and_then(|color| *color)
+	}
+
+	/// Returns whether the given address was occupied when the view was 
+	/// created.
+	pub fn contains(&self, address: Address) -> bool {
+		self.colors.contains_key(&address)
+	}
+
+	/// Returns an iterator over the addresses and colors in the view, in 
+	/// address order.
+	pub fn iter<'a>(&'a self) 
+		-> Box<Iterator<Item=(Address, Option<Color>)> + 'a> 
+	{
+		Box::new(self.colors.iter().map(|(&address, &color)| (address, color)))
+	}
+}